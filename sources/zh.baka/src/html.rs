@@ -7,6 +7,50 @@ use aidoku::{
 
 use crate::url::Url;
 
+/// 依 `.wp-pagenavi` 判斷是否還有下一頁
+///
+/// 有 `span.current` 與最後一頁（數字或 `a.last` 的 `/page/N/`）時比較兩者，
+/// 否則看有沒有 `a.nextpostslink`
+fn has_next_page(document: &Document) -> bool {
+    let page_of = |href: &str| {
+        href.split("/page/")
+            .nth(1)
+            .and_then(|page| page.split(|c: char| !c.is_ascii_digit()).next())
+            .and_then(|page| page.parse::<i32>().ok())
+    };
+
+    let mut current = 0;
+    let mut last = 0;
+
+    if let Some(items) = document.select(".wp-pagenavi > .current, .wp-pagenavi > a") {
+        for item in items {
+            let page = item
+                .text()
+                .unwrap_or_default()
+                .trim()
+                .parse::<i32>()
+                .ok()
+                .or_else(|| page_of(&item.attr("href").unwrap_or_default()));
+
+            if let Some(page) = page {
+                if item.attr("class").unwrap_or_default().contains("current") {
+                    current = page;
+                }
+
+                last = last.max(page);
+            }
+        }
+    }
+
+    if current > 0 && last > 0 {
+        return current < last;
+    }
+
+    document
+        .select_first(".wp-pagenavi > a.nextpostslink, .nav-links .nav-previous > a")
+        .is_some()
+}
+
 pub trait GenManga {
     fn list(&self) -> Result<MangaPageResult>;
     fn detail(&self, manga: &mut Manga) -> Result<()>;
@@ -72,9 +116,11 @@ impl GenManga for Document {
             });
         }
 
+        let has_next_page = !mangas.is_empty() && has_next_page(self);
+
         Ok(MangaPageResult {
-            entries: mangas.clone(),
-            has_next_page: !mangas.is_empty(),
+            entries: mangas,
+            has_next_page,
         })
     }
 
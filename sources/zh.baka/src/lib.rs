@@ -50,6 +50,19 @@ impl Source for Bakamh {
 
         if needs_details {
             GenManga::detail(&response, &mut manga)?;
+
+            let blocked_tags = settings::find_blocked_tags(
+                manga.tags.as_deref().unwrap_or_default(),
+                &settings::get_blocked_tags(),
+            );
+
+            if !blocked_tags.is_empty() {
+                manga.description = Some(format!(
+                    "【已封鎖標籤：{}】\n{}",
+                    blocked_tags.join("、"),
+                    manga.description.unwrap_or_default()
+                ));
+            }
         }
 
         if needs_chapters {
@@ -109,3 +122,6 @@ impl ListingProvider for Bakamh {
 }
 
 register_source!(Bakamh, BaseUrlProvider, ListingProvider, Home);
+
+#[cfg(test)]
+mod test;
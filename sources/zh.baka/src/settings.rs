@@ -1,9 +1,10 @@
 use aidoku::{
-    alloc::string::String,
+    alloc::{String, Vec},
     imports::defaults::{DefaultValue, defaults_get, defaults_set},
 };
 
 const BASE_URL_KEY: &str = "url";
+const BLOCKED_TAGS_KEY: &str = "blockedTags";
 
 pub fn get_base_url() -> String {
     let mut base_url = defaults_get::<String>(BASE_URL_KEY).unwrap_or_default();
@@ -21,3 +22,20 @@ pub fn get_base_url() -> String {
 
     base_url
 }
+
+pub fn get_blocked_tags() -> Vec<String> {
+    defaults_get::<Vec<String>>(BLOCKED_TAGS_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// 回傳 `tags` 中被封鎖的標籤
+pub fn find_blocked_tags(tags: &[String], blocked_tags: &[String]) -> Vec<String> {
+    tags.iter()
+        .filter(|tag| blocked_tags.contains(&tag.trim().to_lowercase()))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,33 @@
+#![expect(clippy::unwrap_used)]
+
+use super::*;
+use aidoku::imports::html::Html;
+use aidoku_test::aidoku_test;
+
+#[aidoku_test]
+fn test_list_has_next_page() {
+    // 手寫的範例，仿照分類頁 wp-pagenavi 分頁的版面：共 40 頁的第 1、20、40 頁
+    let pages = [
+        (
+            r#"<div id="loop-content"><div class="c-image-hover"><a href="https://bakamh.com/manga/wanmei-de-ta/" title="完美的她"><img class="img-responsive" src="https://bakamh.com/wp-content/uploads/2024/03/wanmei-de-ta-175x238.jpg"></a><span class="img-responsive">韩漫</span></div></div>
+            <div class="wp-pagenavi" role="navigation"><span class="pages">第 1 頁，共 40 頁</span><span aria-current="page" class="current">1</span><a class="page larger" title="第 2 頁" href="https://bakamh.com/manga-genre/hanman/page/2/">2</a><span class="extend">...</span><a class="nextpostslink" rel="next" aria-label="下一頁" href="https://bakamh.com/manga-genre/hanman/page/2/">»</a><a class="last" aria-label="最後一頁" href="https://bakamh.com/manga-genre/hanman/page/40/">最後 »</a></div>"#,
+            true,
+        ),
+        (
+            r#"<div id="loop-content"><div class="c-image-hover"><a href="https://bakamh.com/manga/wanmei-de-ta/" title="完美的她"><img class="img-responsive" src="https://bakamh.com/wp-content/uploads/2024/03/wanmei-de-ta-175x238.jpg"></a><span class="img-responsive">韩漫</span></div></div>
+            <div class="wp-pagenavi" role="navigation"><span class="pages">第 20 頁，共 40 頁</span><a class="first" aria-label="第一頁" href="https://bakamh.com/manga-genre/hanman/">« 最前</a><a class="previouspostslink" rel="prev" aria-label="上一頁" href="https://bakamh.com/manga-genre/hanman/page/19/">«</a><span class="extend">...</span><a class="page smaller" title="第 19 頁" href="https://bakamh.com/manga-genre/hanman/page/19/">19</a><span aria-current="page" class="current">20</span><a class="page larger" title="第 21 頁" href="https://bakamh.com/manga-genre/hanman/page/21/">21</a><span class="extend">...</span><a class="nextpostslink" rel="next" aria-label="下一頁" href="https://bakamh.com/manga-genre/hanman/page/21/">»</a><a class="last" aria-label="最後一頁" href="https://bakamh.com/manga-genre/hanman/page/40/">最後 »</a></div>"#,
+            true,
+        ),
+        (
+            r#"<div id="loop-content"><div class="c-image-hover"><a href="https://bakamh.com/manga/wanmei-de-ta/" title="完美的她"><img class="img-responsive" src="https://bakamh.com/wp-content/uploads/2024/03/wanmei-de-ta-175x238.jpg"></a><span class="img-responsive">韩漫</span></div></div>
+            <div class="wp-pagenavi" role="navigation"><span class="pages">第 40 頁，共 40 頁</span><a class="first" aria-label="第一頁" href="https://bakamh.com/manga-genre/hanman/">« 最前</a><a class="previouspostslink" rel="prev" aria-label="上一頁" href="https://bakamh.com/manga-genre/hanman/page/39/">«</a><span class="extend">...</span><a class="page smaller" title="第 39 頁" href="https://bakamh.com/manga-genre/hanman/page/39/">39</a><span aria-current="page" class="current">40</span></div>"#,
+            false,
+        ),
+    ];
+
+    for (page, has_next_page) in pages {
+        let document = Html::parse(page).unwrap();
+
+        assert_eq!(document.list().unwrap().has_next_page, has_next_page);
+    }
+}
@@ -2,10 +2,14 @@ use aidoku::{
     Result,
     alloc::String,
     imports::net::{HttpMethod, Request},
+    prelude::*,
 };
 
 use crate::settings;
 
+/// 選擇語言時送出的語言 Cookie，名稱尚未對照網站確認
+const LANGUAGE_COOKIE: &str = "dm5_lang";
+
 pub struct Fetch;
 
 impl Fetch {
@@ -14,10 +18,41 @@ impl Fetch {
 
         Ok(Request::new(url, method)?
             .header("User-Agent", &user_agent)
-            .header("Cookie", "isAdult=1"))
+            .header("Cookie", &Fetch::cookie()))
+    }
+
+    /// `isAdult=1`、選擇的語言，加上登入後的 Cookie
+    pub fn cookie() -> String {
+        let session_cookies = settings::get_session_cookies();
+
+        let mut cookie = String::from("isAdult=1");
+
+        if let Some(language) = settings::get_language_cookie() {
+            cookie.push_str(&format!("; {}={}", LANGUAGE_COOKIE, language));
+        }
+
+        let language_prefix = format!("{}=", LANGUAGE_COOKIE);
+
+        for pair in session_cookies.split(';') {
+            let pair = pair.trim();
+
+            if pair.is_empty() || pair.starts_with("isAdult=") || pair.starts_with(&language_prefix)
+            {
+                continue;
+            }
+
+            cookie.push_str("; ");
+            cookie.push_str(pair);
+        }
+
+        cookie
     }
 
     pub fn get(url: String) -> Result<Request> {
         Fetch::request(url, HttpMethod::Get)
     }
+
+    pub fn get_mobile(url: String) -> Result<Request> {
+        Ok(Fetch::get(url)?.header("User-Agent", &settings::get_mobile_user_agent()))
+    }
 }
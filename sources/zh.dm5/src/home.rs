@@ -1,150 +1,81 @@
 use aidoku::{
     Home, HomeComponent, HomeComponentValue, HomeLayout, HomePartialResult, Listing, ListingKind,
-    Manga, Result,
-    alloc::{String, Vec, string::ToString as _, vec},
-    error,
-    imports::{
-        net::{Request, RequestError, Response},
-        std::send_partial_result,
-    },
+    Result,
+    alloc::{Vec, string::ToString as _},
+    imports::{net::Request, std::send_partial_result},
 };
 
-use crate::{Dm5, fetch::Fetch, html::GenManga, url::Url};
+use dm5_engine::{html::GenManga, rank};
+
+use crate::{Dm5, fetch::Fetch};
+
+/// 首頁區塊：(listing id, 標題)，第一項以大卷軸顯示
+const SECTIONS: &[(&str, &str)] = &[
+    ("dailymanga", "今日更新"),
+    ("jpmanga", "日漫排行"),
+    ("cnmanga", "陸漫排行"),
+    ("allmanga", "綜合排行"),
+    ("risemanga", "上升最快"),
+    ("newmanga", "新作排行"),
+    ("collectmanga", "收藏排行"),
+    ("commentmanga", "吐槽排行"),
+    ("allmanga-day", "綜合日榜"),
+    ("allmanga-week", "綜合週榜"),
+    ("allmanga-month", "綜合月榜"),
+    ("allmanga-all", "綜合總榜"),
+];
 
 impl Home for Dm5 {
     fn get_home(&self) -> Result<HomeLayout> {
         send_partial_result(&HomePartialResult::Layout(HomeLayout {
-            components: vec![
-                HomeComponent {
-                    title: Some("今日更新".to_string()),
-                    subtitle: None,
-                    value: HomeComponentValue::empty_big_scroller(),
-                },
-                HomeComponent {
-                    title: Some("日漫排行".to_string()),
-                    subtitle: None,
-                    value: HomeComponentValue::empty_manga_list(),
-                },
-                HomeComponent {
-                    title: Some("陸漫排行".to_string()),
-                    subtitle: None,
-                    value: HomeComponentValue::empty_manga_list(),
-                },
-                HomeComponent {
-                    title: Some("綜合排行".to_string()),
-                    subtitle: None,
-                    value: HomeComponentValue::empty_manga_list(),
-                },
-                HomeComponent {
-                    title: Some("上升最快".to_string()),
+            components: SECTIONS
+                .iter()
+                .enumerate()
+                .map(|(idx, (_, title))| HomeComponent {
+                    title: Some(title.to_string()),
                     subtitle: None,
-                    value: HomeComponentValue::empty_manga_list(),
-                },
-            ],
+                    value: if idx == 0 {
+                        HomeComponentValue::empty_big_scroller()
+                    } else {
+                        HomeComponentValue::empty_manga_list()
+                    },
+                })
+                .collect(),
         }));
 
-        let responses: [core::result::Result<Response, RequestError>; 5] = Request::send_all([
-            Fetch::get(Url::rank("new".to_string())?.to_string())?,
-            Fetch::get(Url::rank("2".to_string())?.to_string())?,
-            Fetch::get(Url::rank("1".to_string())?.to_string())?,
-            Fetch::get(Url::rank("3".to_string())?.to_string())?,
-            Fetch::get(Url::rank("7".to_string())?.to_string())?,
-        ])
-        .try_into()
-        .map_err(|_| error!("Failed to convert requests vec to array"))?;
+        let params = Dm5::params();
 
-        let results: [Result<Vec<Manga>>; 5] = responses
-            .map(|res| res?.get_html()?.list())
-            .map(|res| Ok(res?.entries));
+        let requests = SECTIONS
+            .iter()
+            .map(|(id, _)| Fetch::get(rank::url(&params, id, 1)?))
+            .collect::<Result<Vec<Request>>>()?;
 
-        let [daily_rank, jpmanga_rank, cnmanga_rank, all_rank, rise_rank] = results;
-        let dailymanga = daily_rank?;
-        let jpmanga = jpmanga_rank?;
-        let cnmanga = cnmanga_rank?;
-        let allmanga = all_rank?;
-        let risemanga = rise_rank?;
+        let responses = Request::send_all(requests);
 
         let mut components = Vec::new();
 
-        if !dailymanga.is_empty() {
-            components.push(HomeComponent {
-                title: Some("今日更新".to_string()),
-                subtitle: None,
-                value: HomeComponentValue::MangaList {
-                    ranking: true,
-                    page_size: Some(1),
-                    entries: dailymanga.into_iter().map(|manga| manga.into()).collect(),
-                    listing: Some(Listing {
-                        id: "dailymanga".to_string(),
-                        name: "今日更新".to_string(),
-                        kind: ListingKind::Default,
-                    }),
-                },
-            });
-        }
-
-        if !jpmanga.is_empty() {
-            components.push(HomeComponent {
-                title: Some("日漫排行".to_string()),
-                subtitle: None,
-                value: HomeComponentValue::MangaList {
-                    ranking: true,
-                    page_size: Some(3),
-                    entries: jpmanga.into_iter().map(|manga| manga.into()).collect(),
-                    listing: Some(Listing {
-                        id: "jpmanga".to_string(),
-                        name: "日漫排行".to_string(),
-                        kind: ListingKind::Default,
-                    }),
-                },
-            });
-        }
-
-        if !cnmanga.is_empty() {
-            components.push(HomeComponent {
-                title: Some("陸漫排行".to_string()),
-                subtitle: None,
-                value: HomeComponentValue::MangaList {
-                    ranking: true,
-                    page_size: Some(3),
-                    entries: cnmanga.into_iter().map(|manga| manga.into()).collect(),
-                    listing: Some(Listing {
-                        id: "cnmanga".to_string(),
-                        name: "陸漫排行".to_string(),
-                        kind: ListingKind::Default,
-                    }),
-                },
-            });
-        }
-
-        if !allmanga.is_empty() {
-            components.push(HomeComponent {
-                title: Some("綜合排行".to_string()),
-                subtitle: None,
-                value: HomeComponentValue::MangaList {
-                    ranking: true,
-                    page_size: Some(3),
-                    entries: allmanga.into_iter().map(|manga| manga.into()).collect(),
-                    listing: Some(Listing {
-                        id: "allmanga".to_string(),
-                        name: "綜合排行".to_string(),
-                        kind: ListingKind::Default,
-                    }),
-                },
-            });
-        }
+        for (idx, ((id, title), response)) in SECTIONS.iter().zip(responses).enumerate() {
+            // 單一區塊失敗時略過，不影響其他區塊
+            let Some(entries) = response
+                .ok()
+                .and_then(|response| response.get_html().ok())
+                .and_then(|document| document.list(&params).ok())
+                .map(|result| result.entries)
+                .filter(|entries| !entries.is_empty())
+            else {
+                continue;
+            };
 
-        if !risemanga.is_empty() {
             components.push(HomeComponent {
-                title: Some("上升最快".to_string()),
+                title: Some(title.to_string()),
                 subtitle: None,
                 value: HomeComponentValue::MangaList {
                     ranking: true,
-                    page_size: Some(3),
-                    entries: risemanga.into_iter().map(|manga| manga.into()).collect(),
+                    page_size: Some(if idx == 0 { 1 } else { 3 }),
+                    entries: entries.into_iter().map(|manga| manga.into()).collect(),
                     listing: Some(Listing {
-                        id: "risemanga".to_string(),
-                        name: "上升最快".to_string(),
+                        id: id.to_string(),
+                        name: title.to_string(),
                         kind: ListingKind::Default,
                     }),
                 },
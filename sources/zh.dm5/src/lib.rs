@@ -2,29 +2,80 @@
 extern crate alloc;
 
 mod fetch;
-mod helpers;
 mod home;
-mod html;
 mod settings;
 mod url;
 
 use aidoku::{
-    BaseUrlProvider, Chapter, FilterValue, ImageRequestProvider, Listing, ListingProvider, Manga,
-    MangaPageResult, Page, PageContext, Result, Source,
+    BaseUrlProvider, Chapter, DeepLinkHandler, DeepLinkResult, DynamicListings, FilterValue,
+    HashMap, ImageRequestProvider, Listing, ListingKind, ListingProvider, Manga, MangaPageResult,
+    NotificationHandler, Page, PageContext, Result, Source, Viewer, WebLoginHandler,
     alloc::{String, Vec, string::ToString as _},
     imports::{html::Document, net::Request},
     prelude::*,
 };
 
+use core::cell::RefCell;
+
+use dm5_engine::{Engine, Params, Selectors, helpers, html::GenManga, rank};
+
 use crate::fetch::Fetch;
-use crate::html::GenManga;
 use crate::url::Url;
 
-struct Dm5;
+/// 詳情頁的相關推薦與同作者作品
+struct Recommendations {
+    key: String,
+    related: Vec<Manga>,
+    same_author: Vec<Manga>,
+}
+
+struct Dm5 {
+    engine: Engine,
+    /// 已讀過的推薦作品，新的在前；只是快取，列表以設定中的最近開啟漫畫為準
+    recommendations: RefCell<Vec<Recommendations>>,
+}
+
+impl Dm5 {
+    /// 目前設定下的引擎參數
+    fn params() -> Params {
+        Params {
+            base_url: settings::get_base_url(),
+            domains: url::DOMAINS,
+            language: settings::get_language(),
+            selectors: &Selectors::DM5,
+            viewer: settings::get_viewer().unwrap_or(Viewer::Unknown),
+            hide_locked_chapters: settings::get_hide_locked_chapters(),
+            lazy_pages: settings::get_lazy_pages(),
+            mobile: settings::get_mobile_backend(),
+            manga_key: helpers::manga_key_of,
+            request: Fetch::get,
+            mobile_request: Fetch::get_mobile,
+        }
+    }
+
+    /// 從已取得的詳情頁快取推薦作品，列表不必再讀一次詳情頁
+    fn cache_recommendations(&self, key: &str, document: &Document, params: &Params) {
+        let mut recommendations = self.recommendations.borrow_mut();
+
+        recommendations.retain(|item| item.key != key);
+        recommendations.insert(
+            0,
+            Recommendations {
+                key: key.to_string(),
+                related: document.recommendations(params, helpers::RELATED_HEADINGS),
+                same_author: document.recommendations(params, helpers::SAME_AUTHOR_HEADINGS),
+            },
+        );
+        recommendations.truncate(settings::MAX_RECENT_MANGA);
+    }
+}
 
 impl Source for Dm5 {
     fn new() -> Self {
-        Self
+        Self {
+            engine: Engine::new(),
+            recommendations: RefCell::new(Vec::new()),
+        }
     }
 
     fn get_search_manga_list(
@@ -37,7 +88,7 @@ impl Source for Dm5 {
 
         let response = Fetch::get(url)?.html()?;
 
-        GenManga::list(&response)
+        GenManga::list(&response, &Dm5::params())
     }
 
     fn get_manga_update(
@@ -50,23 +101,61 @@ impl Source for Dm5 {
 
         let response = Fetch::get(url)?.html()?;
 
+        let params = Dm5::params();
+
         if needs_details {
-            GenManga::detail(&response, &mut manga)?;
+            GenManga::detail(&response, &params, &mut manga)?;
+
+            // 背景更新通常只要求詳情或章節其一，兩者都要時才是開啟詳情頁
+            if needs_chapters {
+                settings::add_recent_manga(&manga.key, &manga.title);
+                self.cache_recommendations(&manga.key, &response, &params);
+            }
+
+            // 地區與標籤無法判斷時，參考第一話閱讀頁的閱讀模式
+            if let Some(viewer) = settings::get_viewer() {
+                manga.viewer = viewer;
+            } else if matches!(manga.viewer, Viewer::Unknown) {
+                manga.viewer = self
+                    .engine
+                    .read_mode_viewer(&params, &manga.key, &response)
+                    .unwrap_or(Viewer::Webtoon);
+            }
+
+            let blocked_tags = settings::find_blocked_tags(
+                manga.tags.as_deref().unwrap_or_default(),
+                &settings::get_blocked_tags(),
+            );
+
+            if !blocked_tags.is_empty() {
+                manga.description = Some(format!(
+                    "【已封鎖標籤：{}】\n{}",
+                    blocked_tags.join("、"),
+                    manga.description.unwrap_or_default()
+                ));
+            }
         }
 
         if needs_chapters {
-            manga.chapters = Some(GenManga::chapters(&response)?);
+            manga.chapters = Some(GenManga::chapters(&response, &params)?);
         }
 
         Ok(manga)
     }
 
     fn get_page_list(&self, _: Manga, chapter: Chapter) -> Result<Vec<Page>> {
-        let url = Url::chapter(chapter.key.clone())?.to_string();
+        // 登入後已購買的章節可能仍帶著舊的鎖定狀態，交由閱讀頁判斷
+        if chapter.locked && !settings::is_logged_in() {
+            bail!("此章節需要付費解鎖");
+        }
 
-        let response = Fetch::get(url.clone())?.string()?;
+        self.engine.page_list(&Dm5::params(), &chapter.key)
+    }
+}
 
-        <Document as GenManga>::chapter(url, response)
+impl DeepLinkHandler for Dm5 {
+    fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
+        dm5_engine::deep_link(&Dm5::params(), &url)
     }
 }
 
@@ -77,54 +166,153 @@ impl BaseUrlProvider for Dm5 {
 }
 
 impl ImageRequestProvider for Dm5 {
-    fn get_image_request(&self, url: String, _context: Option<PageContext>) -> Result<Request> {
-        let cid = url
-            .split("cid=")
-            .nth(1)
-            .and_then(|s| s.split('&').next())
-            .unwrap_or("");
-
-        let referer = if cid.is_empty() {
-            url.clone()
-        } else {
-            Url::chapter(format!("m{}", cid))?.to_string()
-        };
-
-        Ok(Fetch::get(url)?
-            .header("Accept-Language", "zh-TW")
-            .header("Referer", &referer))
+    fn get_image_request(&self, url: String, context: Option<PageContext>) -> Result<Request> {
+        Ok(self
+            .engine
+            .image_request(&Dm5::params(), url, context)?
+            .header("Accept-Language", settings::get_accept_language()))
+    }
+}
+
+impl WebLoginHandler for Dm5 {
+    fn handle_web_login(&self, _key: String, cookies: HashMap<String, String>) -> Result<bool> {
+        let session_cookies = cookies
+            .iter()
+            .filter(|(name, _)| name.as_str() != "isAdult")
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<String>>()
+            .join("; ");
+
+        if session_cookies.is_empty() {
+            return Ok(false);
+        }
+
+        settings::set_session_cookies(&session_cookies);
+
+        if !dm5_engine::verify_login(&Dm5::params())? {
+            settings::set_session_cookies("");
+
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
+
+impl NotificationHandler for Dm5 {
+    fn handle_notification(&self, notification: String) {
+        if notification.as_str() == "logout" {
+            settings::set_session_cookies("");
+        }
+    }
+}
+
+impl DynamicListings for Dm5 {
+    fn get_dynamic_listings(&self) -> Result<Vec<Listing>> {
+        let mut listings = Vec::new();
+
+        // 書架只在登入後顯示
+        if settings::is_logged_in() {
+            listings.push(Listing {
+                id: String::from("bookshelf"),
+                name: String::from("我的書架"),
+                kind: ListingKind::Default,
+            });
+        }
+
+        listings.extend(rank::listings());
+
+        for (key, title) in settings::get_recent_manga() {
+            listings.push(Listing {
+                id: format!("related:{}", key),
+                name: format!("相關推薦：{}", title),
+                kind: ListingKind::Default,
+            });
+            listings.push(Listing {
+                id: format!("sameauthor:{}", key),
+                name: format!("同作者作品：{}", title),
+                kind: ListingKind::Default,
+            });
+        }
+
+        Ok(listings)
     }
 }
 
 impl ListingProvider for Dm5 {
     fn get_manga_list(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
-        if page > 1 {
-            return Ok(MangaPageResult::default());
+        // 詳情頁的相關推薦、同作者作品，只有一頁
+        if let Some((kind, key)) = listing.id.split_once(':') {
+            let entries_of: fn(&Recommendations) -> &Vec<Manga> = match kind {
+                "related" => |item| &item.related,
+                "sameauthor" => |item| &item.same_author,
+                _ => bail!("Invalid listing"),
+            };
+
+            if page > 1 {
+                return Ok(MangaPageResult::default());
+            }
+
+            let cached = || {
+                self.recommendations
+                    .borrow()
+                    .iter()
+                    .find(|item| item.key == key)
+                    .map(|item| entries_of(item).clone())
+            };
+
+            // 來源重新載入後沒有快取，才重新讀取詳情頁
+            let entries = match cached() {
+                Some(entries) => entries,
+                None => {
+                    let url = Url::book(key.to_string())?.to_string();
+
+                    let response = Fetch::get(url)?.html()?;
+
+                    self.cache_recommendations(key, &response, &Dm5::params());
+
+                    cached().unwrap_or_default()
+                }
+            };
+
+            return Ok(MangaPageResult {
+                entries,
+                has_next_page: false,
+            });
+        }
+
+        if listing.id == "bookshelf" {
+            if !settings::is_logged_in() {
+                bail!("請先在設定中登入動漫屋帳號");
+            }
+
+            let url = Url::bookshelf(page)?.to_string();
+
+            let response = Fetch::get(url)?.html()?;
+
+            return GenManga::list(&response, &Dm5::params());
         }
 
-        let rank = match listing.id.as_str() {
-            "dailymanga" => "new",
-            "cnmanga" => "1",
-            "jpmanga" => "2",
-            "allmanga" => "3",
-            "risemanga" => "7",
-            _ => bail!("Invalid listing"),
-        };
+        let params = Dm5::params();
 
-        let url = Url::rank(rank.to_string())?.to_string();
+        let url = rank::url(&params, &listing.id, page)?;
 
         let response = Fetch::get(url)?.html()?;
 
-        GenManga::list(&response)
+        GenManga::list(&response, &params)
     }
 }
 
 register_source!(
     Dm5,
+    DeepLinkHandler,
     BaseUrlProvider,
     ImageRequestProvider,
+    WebLoginHandler,
+    NotificationHandler,
     Home,
-    ListingProvider
+    ListingProvider,
+    DynamicListings
 );
 
 #[cfg(test)]
@@ -1,10 +1,25 @@
 use aidoku::{
-    alloc::string::String,
+    Viewer,
+    alloc::{String, Vec},
     imports::defaults::{DefaultValue, defaults_get, defaults_set},
+    prelude::*,
 };
 
 const BASE_URL_KEY: &str = "url";
+const BLOCKED_TAGS_KEY: &str = "blockedTags";
+const HIDE_LOCKED_CHAPTERS_KEY: &str = "hideLockedChapters";
+const SESSION_COOKIES_KEY: &str = "sessionCookies";
+const LAZY_PAGES_KEY: &str = "lazyPages";
 const USER_AGENT_KEY: &str = "userAgent";
+const MOBILE_USER_AGENT_KEY: &str = "mobileUserAgent";
+const LANGUAGE_KEY: &str = "language";
+const BACKEND_KEY: &str = "backend";
+const VIEWER_KEY: &str = "viewer";
+const LOGIN_URL_KEY: &str = "loginUrl";
+const RECENT_MANGA_KEY: &str = "recentManga";
+
+/// 最多記住幾部最近開啟的漫畫
+pub const MAX_RECENT_MANGA: usize = 3;
 
 pub fn get_base_url() -> String {
     let mut base_url = defaults_get::<String>(BASE_URL_KEY).unwrap_or_default();
@@ -20,6 +35,13 @@ pub fn get_base_url() -> String {
         base_url = String::from(default_base_url);
     }
 
+    // 登入頁跟著目前的網域，settings.json 以 urlKey 讀取
+    let login_url = format!("{}/login/", base_url);
+
+    if defaults_get::<String>(LOGIN_URL_KEY).as_ref() != Some(&login_url) {
+        defaults_set(LOGIN_URL_KEY, DefaultValue::String(login_url));
+    }
+
     base_url
 }
 
@@ -39,3 +61,150 @@ pub fn get_user_agent() -> String {
 
     user_agent
 }
+
+/// 手機版的 User-Agent，電腦版的 User-Agent 會被導回電腦版
+pub fn get_mobile_user_agent() -> String {
+    let mut user_agent = defaults_get::<String>(MOBILE_USER_AGENT_KEY).unwrap_or_default();
+
+    if user_agent.is_empty() {
+        let default_user_agent = "Mozilla/5.0 (iPhone; CPU iPhone OS 18_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.4 Mobile/15E148 Safari/604.1";
+
+        defaults_set(
+            MOBILE_USER_AGENT_KEY,
+            DefaultValue::String(String::from(default_user_agent)),
+        );
+
+        user_agent = String::from(default_user_agent);
+    }
+
+    user_agent
+}
+
+pub fn get_blocked_tags() -> Vec<String> {
+    defaults_get::<Vec<String>>(BLOCKED_TAGS_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// 回傳 `tags` 中被封鎖的標籤
+pub fn find_blocked_tags(tags: &[String], blocked_tags: &[String]) -> Vec<String> {
+    tags.iter()
+        .filter(|tag| blocked_tags.contains(&tag.trim().to_lowercase()))
+        .cloned()
+        .collect()
+}
+
+pub fn get_hide_locked_chapters() -> bool {
+    defaults_get::<bool>(HIDE_LOCKED_CHAPTERS_KEY).unwrap_or(false)
+}
+
+/// 登入後保存的 Cookie，格式為 `k1=v1; k2=v2`
+pub fn get_session_cookies() -> String {
+    defaults_get::<String>(SESSION_COOKIES_KEY).unwrap_or_default()
+}
+
+pub fn set_session_cookies(cookies: &str) {
+    defaults_set(
+        SESSION_COOKIES_KEY,
+        DefaultValue::String(String::from(cookies)),
+    );
+}
+
+pub fn is_logged_in() -> bool {
+    !get_session_cookies().is_empty()
+}
+
+/// 逐頁解析圖片網址（預設），關閉時進入章節前一次解析全部頁面
+pub fn get_lazy_pages() -> bool {
+    defaults_get::<bool>(LAZY_PAGES_KEY).unwrap_or(true)
+}
+
+/// 使用者選擇的語言：`1` 簡體、`2` 繁體，`default` 沿用網站預設
+fn get_language_setting() -> String {
+    defaults_get::<String>(LANGUAGE_KEY).unwrap_or_default()
+}
+
+/// 網站的 `language` 參數，沒有選擇語言時為 `1`
+pub fn get_language() -> String {
+    match get_language_setting().as_str() {
+        "2" => String::from("2"),
+        _ => String::from("1"),
+    }
+}
+
+pub fn is_traditional() -> bool {
+    get_language() == "2"
+}
+
+/// 圖片請求的 `Accept-Language`，只有選擇簡體時才改為 `zh-CN`
+pub fn get_accept_language() -> &'static str {
+    match get_language_setting().as_str() {
+        "1" => "zh-CN",
+        _ => "zh-TW",
+    }
+}
+
+/// 有選擇語言時才帶上語言 Cookie 的值
+pub fn get_language_cookie() -> Option<String> {
+    let language = get_language_setting();
+
+    matches!(language.as_str(), "1" | "2").then_some(language)
+}
+
+/// 使用手機版（電腦版網域的 `m.` 子網域）的列表、詳情與閱讀頁
+pub fn get_mobile_backend() -> bool {
+    defaults_get::<String>(BACKEND_KEY).is_some_and(|backend| backend == "mobile")
+}
+
+/// 使用者指定的閱讀方式，`auto` 時回傳 `None` 交由地區與標籤判斷
+pub fn get_viewer() -> Option<Viewer> {
+    match defaults_get::<String>(VIEWER_KEY)
+        .unwrap_or_default()
+        .as_str()
+    {
+        "rtl" => Some(Viewer::RightToLeft),
+        "ltr" => Some(Viewer::LeftToRight),
+        "vertical" => Some(Viewer::Vertical),
+        "webtoon" => Some(Viewer::Webtoon),
+        _ => None,
+    }
+}
+
+/// 最近開啟的漫畫 `(key, 名稱)`，最新的在前，各自對應一組推薦作品的動態列表
+pub fn get_recent_manga() -> Vec<(String, String)> {
+    defaults_get::<Vec<String>>(RECENT_MANGA_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            entry
+                .split_once('\t')
+                .map(|(key, title)| (String::from(key), String::from(title)))
+        })
+        .collect()
+}
+
+/// 記住開啟的漫畫，已記住時移到最前面，沒有變動時不寫入
+pub fn add_recent_manga(key: &str, title: &str) {
+    let mut entries = get_recent_manga();
+
+    if entries
+        .first()
+        .is_some_and(|entry| entry.0 == key && entry.1 == title)
+    {
+        return;
+    }
+
+    entries.retain(|entry| entry.0 != key);
+    entries.insert(0, (String::from(key), title.replace('\t', " ")));
+    entries.truncate(MAX_RECENT_MANGA);
+
+    let value = entries
+        .into_iter()
+        .map(|(key, title)| format!("{}\t{}", key, title))
+        .collect();
+
+    defaults_set(RECENT_MANGA_KEY, DefaultValue::StringArray(value));
+}
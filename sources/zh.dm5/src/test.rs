@@ -1,8 +1,12 @@
-// #![expect(clippy::unwrap_used)]
+#![expect(clippy::unwrap_used)]
 
-// use super::*;
+use super::*;
 // use aidoku::{HashMap, PageContext};
-// use aidoku_test::aidoku_test;
+use aidoku::{
+    alloc::{string::ToString as _, vec},
+    imports::html::Html,
+};
+use aidoku_test::aidoku_test;
 
 // #[aidoku_test]
 // fn test_get_search_manga_list() {
@@ -107,3 +111,310 @@
 
 //     panic!("is_chapter=true 結果: {:#?}", result);
 // }
+
+#[aidoku_test]
+fn test_list_has_next_page() {
+    // 手寫的範例，仿照列表 page-pagination 分頁的版面：共 3 頁的第 1、2、3 頁，
+    // 第 3 頁另放一個指向本頁的「>」，確認不會被當成下一頁
+    let pages = [
+        (
+            r#"<ul class="mh-list col7"><li><div class="mh-item"><a href="/manhua-yaoshenji/" title="妖神记"><p class="mh-cover" style="background-image: url(https://mhfm1tel.cdndm5.com/4/3412/3412_c.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/manhua-yaoshenji/" title="妖神记">妖神记</a></h2></div></div></li></ul>
+            <div class="page-pagination mt20"><div><ul><li><a href="/manhua-list-p1/" class="active">1</a></li><li><a href="/manhua-list-p2/">2</a></li><li><a href="/manhua-list-p3/">3</a></li><li><a href="/manhua-list-p2/">&gt;</a></li></ul></div></div>"#,
+            true,
+        ),
+        (
+            r#"<ul class="mh-list col7"><li><div class="mh-item"><a href="/manhua-yaoshenji/" title="妖神记"><p class="mh-cover" style="background-image: url(https://mhfm1tel.cdndm5.com/4/3412/3412_c.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/manhua-yaoshenji/" title="妖神记">妖神记</a></h2></div></div></li></ul>
+            <div class="page-pagination mt20"><div><ul><li><a href="/manhua-list-p1/">&lt;</a></li><li><a href="/manhua-list-p1/">1</a></li><li><a href="/manhua-list-p2/" class="active">2</a></li><li><a href="/manhua-list-p3/">3</a></li><li><a href="/manhua-list-p3/">&gt;</a></li></ul></div></div>"#,
+            true,
+        ),
+        (
+            r#"<ul class="mh-list col7"><li><div class="mh-item"><a href="/manhua-yaoshenji/" title="妖神记"><p class="mh-cover" style="background-image: url(https://mhfm1tel.cdndm5.com/4/3412/3412_c.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/manhua-yaoshenji/" title="妖神记">妖神记</a></h2></div></div></li></ul>
+            <div class="page-pagination mt20"><div><ul><li><a href="/manhua-list-p2/">&lt;</a></li><li><a href="/manhua-list-p1/">1</a></li><li><a href="/manhua-list-p2/">2</a></li><li><a href="/manhua-list-p3/" class="active">3</a></li><li><a href="/manhua-list-p3/">&gt;</a></li></ul></div></div>"#,
+            false,
+        ),
+    ];
+
+    let params = Dm5::params();
+
+    for (page, has_next_page) in pages {
+        let document = Html::parse(page).unwrap();
+
+        assert_eq!(document.list(&params).unwrap().has_next_page, has_next_page);
+    }
+}
+
+#[aidoku_test]
+fn test_bookshelf_list() {
+    // 書架沿用列表的 mh-list 版面，另外多了更新進度
+    let page = r#"<ul class="mh-list col7"><li><div class="mh-item"><a href="/manhua-yaoshenji/" title="妖神记"><p class="mh-cover" style="background-image: url(https://mhfm1tel.cdndm5.com/4/3412/3412_c.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/manhua-yaoshenji/" title="妖神记">妖神记</a></h2><p class="chapter"><span>更新至</span><a href="/m1217932/">第1话</a></p></div></div></li><li><div class="mh-item"><a href="/manhua-yiquanchaoren/" title="一拳超人"><p class="mh-cover" style="background-image: url(https://mhfm2tel.cdndm5.com/13/12365/12365_c.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/manhua-yiquanchaoren/" title="一拳超人">一拳超人</a></h2></div></div></li></ul>"#;
+
+    let result = Html::parse(page).unwrap().list(&Dm5::params()).unwrap();
+
+    let keys: Vec<&str> = result
+        .entries
+        .iter()
+        .map(|manga| manga.key.as_str())
+        .collect();
+
+    assert_eq!(keys, ["manhua-yaoshenji", "manhua-yiquanchaoren"]);
+    assert_eq!(result.entries[0].title, "妖神记");
+    assert!(!result.has_next_page);
+}
+
+#[aidoku_test]
+fn test_handle_deep_link_manga() {
+    let source = Dm5::new();
+
+    for url in [
+        "https://www.dm5.com/manhua-yaoshenji/",
+        "https://www.dm5.cn/manhua-yaoshenji",
+        "https://www.dm5.com/manhua-yaoshenji/?from=share",
+    ] {
+        let result = source.handle_deep_link(url.to_string()).unwrap();
+
+        assert!(
+            matches!(result, Some(DeepLinkResult::Manga { ref key }) if key == "manhua-yaoshenji"),
+            "{}",
+            url
+        );
+    }
+
+    for url in [
+        "https://www.dm5.com/manhua-list-p2/",
+        "https://www.dm5.com/manhua-rank/?t=2",
+        "https://notdm5.com/manhua-yaoshenji/",
+        "https://www.dm5.com.example.net/manhua-yaoshenji/",
+        "https://example.com/?ref=www.dm5.com/manhua-yaoshenji/",
+    ] {
+        assert!(
+            source.handle_deep_link(url.to_string()).unwrap().is_none(),
+            "{}",
+            url
+        );
+    }
+}
+
+#[aidoku_test]
+fn test_filter_paths() {
+    let select = |id: &str, value: &str| FilterValue::Select {
+        id: id.to_string(),
+        value: value.to_string(),
+    };
+
+    let cases = [
+        // 沒有篩選
+        (vec![], "/manhua-list-p1"),
+        // 「全部」等同未篩選
+        (
+            vec![select("題材", "全部"), select("連載狀態", "全部")],
+            "/manhua-list-p1",
+        ),
+        // 單一篩選使用外部 id
+        (vec![select("題材", "热血")], "/manhua-rexue-p1"),
+        (vec![select("地區", "日韩")], "/manhua-jpkr-p1"),
+        // 繁體名稱（標籤搜尋）
+        (vec![select("genre", "熱血")], "/manhua-rexue-p1"),
+        (
+            vec![select("地區", "日韓"), select("連載狀態", "已完結")],
+            "/manhua-list-area36-st2-p1",
+        ),
+        (
+            vec![
+                select("受眾", "少女向"),
+                FilterValue::Sort {
+                    id: "排序".to_string(),
+                    index: 2,
+                    ascending: false,
+                },
+            ],
+            "/manhua-shaonv-s18-p1",
+        ),
+        // 連載狀態、收費只有 list 形式
+        (vec![select("連載狀態", "连载中")], "/manhua-list-st1-p1"),
+        (vec![select("連載狀態", "已完结")], "/manhua-list-st2-p1"),
+        (vec![select("收費", "免费")], "/manhua-list-pay0-p1"),
+        // 多個篩選使用內部 id
+        (
+            vec![select("題材", "热血"), select("地區", "日韩")],
+            "/manhua-list-area36-tag31-p1",
+        ),
+        (
+            vec![select("題材", "热血"), select("連載狀態", "已完结")],
+            "/manhua-list-tag31-st2-p1",
+        ),
+        (
+            vec![
+                select("地區", "港台"),
+                select("受眾", "少年向"),
+                select("收費", "付费"),
+                select("連載狀態", "连载中"),
+            ],
+            "/manhua-list-area35-group1-pay1-st1-p1",
+        ),
+    ];
+
+    let base_url = settings::get_base_url();
+
+    for (filters, expected) in cases {
+        let url = Url::filters(None, 1, &filters).unwrap().to_string();
+
+        assert_eq!(url.strip_prefix(&base_url), Some(expected));
+    }
+}
+
+#[aidoku_test]
+fn test_chapters_split_tabs() {
+    let body = r#"<html><body>
+        <div class="detail-list-title">
+            <a href="javascript:void(0)" class="block active">连载</a>
+            <a href="javascript:void(0)" class="block">单行本</a>
+        </div>
+        <ul class="view-win-list detail-list-select" id="detail-list-select-1">
+            <li><a href="/m1000003/">第12.5话<span>（20P）</span></a></li>
+            <li><a href="/m1000002/">番外篇</a></li>
+            <div class="chapteritem">
+                <li><a href="/m1000001/">第一话</a></li>
+            </div>
+        </ul>
+        <ul class="view-win-list detail-list-select" id="detail-list-select-2">
+            <li><a href="/m2000002/">第2卷</a><ul><li>附錄</li></ul></li>
+            <li><a href="/m2000001/">第1卷</a></li>
+        </ul>
+    </body></html>"#;
+
+    let chapters = Html::parse(body).unwrap().chapters(&Dm5::params()).unwrap();
+
+    let numbers: Vec<(&str, Option<f32>, Option<f32>)> = chapters
+        .iter()
+        .map(|chapter| {
+            (
+                chapter.key.as_str(),
+                chapter.chapter_number,
+                chapter.volume_number,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        numbers,
+        [
+            ("m1000003", Some(12.5), None),
+            ("m1000002", None, None),
+            ("m1000001", Some(1.0), None),
+            ("m2000002", None, Some(2.0)),
+            ("m2000001", None, Some(1.0)),
+        ]
+    );
+
+    // 頁數附在標題後面，不影響話數解析
+    assert_eq!(chapters[0].title.as_deref(), Some("第12.5话（20P）"));
+    assert!(chapters[0].scanlators.is_none());
+}
+
+#[aidoku_test]
+fn test_recommendations() {
+    let html = r#"
+        <div class="index-title"><h2>同作者作品</h2></div>
+        <ul class="mh-list col3">
+            <li><div class="mh-item"><a href="/manhua-a/" title="作品A"><p class="mh-cover" style="background-image: url(https://img.example/a.jpg)"></p></a></div></li>
+            <li><div class="mh-item"><a href="https://www.dm5.com/manhua-b/" title="作品B"><img src="https://img.example/b.jpg"></a></div></li>
+        </ul>
+        <div class="index-title"><h2>相關推薦</h2></div>
+        <ul class="mh-list col7">
+            <li><a href="/manhua-c/"><img data-src="https://img.example/c.jpg"><h2 class="title">作品C</h2></a></li>
+            <li><a href="/manhua-c/" title="作品C"></a></li>
+            <li><a href="/manhua-list/" title="列表"></a></li>
+        </ul>
+    "#;
+
+    let document = Html::parse(html).unwrap();
+    let parse = |headings| document.recommendations(&Dm5::params(), headings);
+
+    let same_author = parse(helpers::SAME_AUTHOR_HEADINGS);
+    let keys: Vec<&str> = same_author.iter().map(|m| m.key.as_str()).collect();
+    assert_eq!(keys, ["manhua-a", "manhua-b"]);
+    assert_eq!(same_author[0].title, "作品A");
+    assert_eq!(
+        same_author[0].cover.as_deref(),
+        Some("https://img.example/a.jpg")
+    );
+    assert_eq!(
+        same_author[1].cover.as_deref(),
+        Some("https://img.example/b.jpg")
+    );
+
+    let related = parse(helpers::RELATED_HEADINGS);
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].key, "manhua-c");
+    assert_eq!(related[0].title, "作品C");
+    assert_eq!(
+        related[0].cover.as_deref(),
+        Some("https://img.example/c.jpg")
+    );
+
+    // 標題只出現在內文時不算區塊
+    let document = Html::parse(
+        r#"<p class="content">相關推薦</p><ul><li><a href="/manhua-d/" title="作品D"></a></li></ul>"#,
+    )
+    .unwrap();
+    assert!(
+        document
+            .recommendations(&Dm5::params(), helpers::RELATED_HEADINGS)
+            .is_empty()
+    );
+}
+
+#[aidoku_test]
+fn test_recent_manga_listings() {
+    for index in 0..5 {
+        settings::add_recent_manga(&format!("manhua-{}", index), &format!("作品{}", index));
+    }
+    settings::add_recent_manga("manhua-2", "作品2");
+
+    // 重新開啟的移到最前面，只留最近的幾部
+    assert_eq!(
+        settings::get_recent_manga(),
+        [
+            ("manhua-2".to_string(), "作品2".to_string()),
+            ("manhua-4".to_string(), "作品4".to_string()),
+            ("manhua-3".to_string(), "作品3".to_string()),
+        ]
+    );
+
+    // 沒有快取時也列出記住的漫畫
+    let listings = Dm5::new().get_dynamic_listings().unwrap();
+    let ids: Vec<&str> = listings
+        .iter()
+        .map(|listing| listing.id.as_str())
+        .filter(|id| id.contains(':'))
+        .collect();
+
+    assert_eq!(
+        ids,
+        [
+            "related:manhua-2",
+            "sameauthor:manhua-2",
+            "related:manhua-4",
+            "sameauthor:manhua-4",
+            "related:manhua-3",
+            "sameauthor:manhua-3",
+        ]
+    );
+}
+
+#[aidoku_test]
+fn test_author_search_path() {
+    let url = Url::filters(
+        None,
+        2,
+        &[FilterValue::Text {
+            id: "作者".to_string(),
+            value: "ONE".to_string(),
+        }],
+    )
+    .unwrap()
+    .to_string();
+
+    assert!(url.contains("/search?title=ONE&"), "{}", url);
+    assert!(url.contains("&type=2&page=2"), "{}", url);
+}
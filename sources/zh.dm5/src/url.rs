@@ -5,6 +5,8 @@ use aidoku::{
     prelude::*,
 };
 
+use dm5_engine::filters::{FilterSelection, FilterTable, FilterTables};
+
 use crate::settings;
 
 const TAGS: &[&str] = &[
@@ -84,41 +86,107 @@ const OPTIONS_TAGS: &[&str] = &[
     "限制级",
 ];
 
+/// 繁體介面的標籤名稱，順序與 `OPTIONS_TAGS` 相同
+const OPTIONS_TAGS_HANT: &[&str] = &[
+    "全部",
+    "熱血",
+    "戀愛",
+    "校園",
+    "百合",
+    "彩虹",
+    "冒險",
+    "後宮",
+    "科幻",
+    "戰爭",
+    "懸疑",
+    "推理",
+    "搞笑",
+    "奇幻",
+    "魔法",
+    "恐怖",
+    "神鬼",
+    "歷史",
+    "同人",
+    "運動",
+    "紳士",
+    "機甲",
+    "限制級",
+];
+
 const OPTIONS_AREAS: &[&str] = &["全部", "港台", "日韩", "大陆", "欧美"];
 
+const OPTIONS_AREAS_HANT: &[&str] = &["全部", "港台", "日韓", "大陸", "歐美"];
+
 const OPTIONS_AUDIENCES: &[&str] = &["全部", "少年向", "少女向", "青年向"];
 
+const STATUSES: &[&str] = &["", "st1", "st2"];
+
+const OPTIONS_STATUSES: &[&str] = &["全部", "连载中", "已完结"];
+
+const OPTIONS_STATUSES_HANT: &[&str] = &["全部", "連載中", "已完結"];
+
+const PAYS: &[&str] = &["", "pay0", "pay1", "pay2"];
+
+const OPTIONS_PAYS: &[&str] = &["全部", "免费", "付费", "VIP付费"];
+
+const OPTIONS_PAYS_HANT: &[&str] = &["全部", "免費", "付費", "VIP付費"];
+
 const OPTIONS_WORDS: &[&str] = &[
     "全部", "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q",
     "R", "S", "T", "U", "V", "W", "X", "Y", "Z", "0-9",
 ];
 
-#[derive(Clone)]
-pub enum Url {
-    Filter {
-        main_sort: String,
-        sort: String,
-        tag: String,
-        area: String,
-        status: String,
-        audience: String,
-        pay: String,
-        word: String,
-        page: String,
+/// 篩選選項名稱與網址 id 的對照
+const FILTERS: FilterTables = FilterTables {
+    areas: FilterTable {
+        ids: AREAS,
+        slugs: FILTER_AREAS,
+        hans: OPTIONS_AREAS,
+        hant: OPTIONS_AREAS_HANT,
+    },
+    words: FilterTable {
+        ids: WORDS,
+        slugs: FILTER_WORDS,
+        hans: OPTIONS_WORDS,
+        hant: OPTIONS_WORDS,
     },
-    Search {
-        query: String,
-        page: i32,
+    tags: FilterTable {
+        ids: TAGS,
+        slugs: FILTER_TAGS,
+        hans: OPTIONS_TAGS,
+        hant: OPTIONS_TAGS_HANT,
     },
-    Chapter {
-        id: String,
+    audiences: FilterTable {
+        ids: AUDIENCES,
+        slugs: FILTER_AUDIENCES,
+        hans: OPTIONS_AUDIENCES,
+        hant: OPTIONS_AUDIENCES,
     },
-    Book {
-        id: String,
+    // 連載狀態與收費沒有單一篩選的網址，只能放在 "list-" 之後
+    pays: FilterTable {
+        ids: PAYS,
+        slugs: &[],
+        hans: OPTIONS_PAYS,
+        hant: OPTIONS_PAYS_HANT,
     },
-    Rank {
-        id: String,
+    statuses: FilterTable {
+        ids: STATUSES,
+        slugs: &[],
+        hans: OPTIONS_STATUSES,
+        hant: OPTIONS_STATUSES_HANT,
     },
+};
+
+/// 網站的網域，深層連結只處理這些網域與其子網域
+pub const DOMAINS: &[&str] = &["dm5.com", "dm5.cn"];
+
+#[derive(Clone)]
+pub enum Url {
+    Filter { path: String },
+    Search { query: String, page: i32 },
+    AuthorSearch { query: String, page: i32 },
+    Book { id: String },
+    Bookshelf { page: i32 },
 }
 
 impl Url {
@@ -126,40 +194,33 @@ impl Url {
         let base_url = settings::get_base_url();
 
         match self {
-            Self::Chapter { id } => {
-                format!("{}/{}", base_url, id)
-            }
             Self::Book { id } => {
                 format!("{}/{}", base_url, id)
             }
             Self::Search { query, page } => {
                 format!(
-                    "{}/search?title={}&language=1&page={}",
-                    base_url, query, page
+                    "{}/search?title={}&language={}&page={}",
+                    base_url,
+                    query,
+                    settings::get_language(),
+                    page
                 )
             }
-            Self::Filter {
-                main_sort,
-                sort,
-                tag,
-                area,
-                status,
-                audience,
-                pay,
-                word,
-                page,
-            } => {
+            // 搜尋頁的作者模式
+            Self::AuthorSearch { query, page } => {
                 format!(
-                    "{}/manhua{}{}{}{}{}{}{}{}{}",
-                    base_url, main_sort, area, word, tag, audience, pay, status, sort, page
+                    "{}/search?title={}&language={}&type=2&page={}",
+                    base_url,
+                    query,
+                    settings::get_language(),
+                    page
                 )
             }
-            Self::Rank { id } => {
-                if id != "new" {
-                    return format!("{}/manhua-rank/?t={}", base_url, id);
-                }
-
-                format!("{}/manhua-{}", base_url, id)
+            Self::Filter { path } => {
+                format!("{}/manhua{}", base_url, path)
+            }
+            Self::Bookshelf { page } => {
+                format!("{}/bookshelf-p{}/", base_url, page)
             }
         }
     }
@@ -172,16 +233,16 @@ impl Url {
             });
         }
 
-        let mut sort = String::from("");
-        let mut tag = String::from("");
-        let mut area = String::from("");
-        let mut status = String::from("");
-        let mut audience = String::from("");
-        let mut pay = String::from("");
-        let mut word = String::from("");
+        let mut selection = FilterSelection::default();
 
         for filter in filters {
             match filter {
+                FilterValue::Text { id, value } if id == "作者" || id == "author" => {
+                    return Ok(Self::AuthorSearch {
+                        query: encode_uri(value.trim()),
+                        page,
+                    });
+                }
                 FilterValue::Text { value, .. } => {
                     return Ok(Self::Search {
                         query: encode_uri(value.clone()),
@@ -189,106 +250,38 @@ impl Url {
                     });
                 }
                 FilterValue::Sort { index, .. } => {
-                    sort = match index {
-                        0 => "-s2".to_string(),
-                        2 => "-s18".to_string(),
+                    selection.sort = match index {
+                        0 => "s2".to_string(),
+                        2 => "s18".to_string(),
                         _ => "".to_string(),
                     };
                 }
                 FilterValue::Select { id, value } => match id.as_str() {
-                    "排序" => {
-                        sort = if value.is_empty() {
-                            String::new()
-                        } else {
-                            format!("-{}", value.clone())
-                        }
-                    }
-                    "題材" => tag = value.clone(),
-                    "地區" => area = value.clone(),
-                    "受眾" => audience = value.clone(),
-                    "收費" => {
-                        pay = if value.is_empty() {
-                            String::new()
-                        } else {
-                            format!("-{}", value.clone())
-                        }
-                    }
-                    "字母" => word = value.clone(),
-                    "genre" => tag = value.clone(),
+                    "排序" => selection.sort = value.clone(),
+                    "題材" => selection.tag = value.clone(),
+                    "地區" => selection.area = value.clone(),
+                    "受眾" => selection.audience = value.clone(),
+                    "連載狀態" => selection.status = value.clone(),
+                    "收費" => selection.pay = value.clone(),
+                    "字母" => selection.word = value.clone(),
+                    "genre" => selection.tag = value.clone(),
                     _ => continue,
                 },
                 _ => continue,
             }
         }
 
-        let page = format!("-p{}", page);
-
-        // 計算有幾個非空的篩選條件
-        let active = [&tag, &area, &status, &audience, &pay, &word]
-            .iter()
-            .filter(|v| !v.is_empty())
-            .count();
-
-        // 決定 sort 前綴 + 是否轉換
-        let main_sort = match (sort.as_str(), active) {
-            // (_, 0) => "-list".to_string(),                     // 全空 → "list"
-            // (s, _) if !s.is_empty() => format!("-list-{}", s), // sort 非空 → "list-s2"
-            (_, 1) => String::new(),  // 只有 1 個篩選，用 filter ids
-            _ => "-list".to_string(), // 2+ 個篩選 → 固定 "list-"
-        };
-
-        // 透過 options 的 index 查找對應的 ids 或內部 id
-        let convert = |value: &str, options: &[&str], target_ids: &[&str]| {
-            options
-                .iter()
-                .position(|&opt| opt == value)
-                .and_then(|idx| target_ids.get(idx))
-                .map(|&s| s.to_string())
-                .unwrap_or_default()
-        };
-
-        // 根據 active 數量決定轉換到外部 id (`rexue`) 還是內部 id (`tag31`)
-        if active > 0 {
-            let (tags, areas, audiences, words) = if active == 1 {
-                (FILTER_TAGS, FILTER_AREAS, FILTER_AUDIENCES, FILTER_WORDS)
-            } else {
-                (TAGS, AREAS, AUDIENCES, WORDS)
-            };
-
-            tag = convert(&tag, OPTIONS_TAGS, tags);
-            area = convert(&area, OPTIONS_AREAS, areas);
-            audience = convert(&audience, OPTIONS_AUDIENCES, audiences);
-            word = convert(&word, OPTIONS_WORDS, words);
-        }
+        // 標籤搜尋會帶入網站顯示的名稱，先比對目前語言，再比對另一種
+        let path = FILTERS.path(&selection, settings::is_traditional(), page);
 
-        let prefix = |s: String| if s.is_empty() { s } else { format!("-{s}") };
-        let tag = prefix(tag);
-        let area = prefix(area);
-        let audience = prefix(audience);
-        let word = prefix(word);
-
-        Ok(Self::Filter {
-            main_sort,
-            sort,
-            tag,
-            area,
-            status,
-            audience,
-            pay,
-            word,
-            page,
-        })
+        Ok(Self::Filter { path })
     }
 
     pub fn book(id: String) -> Result<Self> {
         Ok(Self::Book { id })
     }
 
-    pub fn chapter(id: String) -> Result<Self> {
-        Ok(Self::Chapter { id })
-    }
-
-    pub fn rank(id: String) -> Result<Self> {
-        Ok(Self::Rank { id })
+    pub fn bookshelf(page: i32) -> Result<Self> {
+        Ok(Self::Bookshelf { page })
     }
 }
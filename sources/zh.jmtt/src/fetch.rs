@@ -1,21 +1,117 @@
 use aidoku::{
-    Result,
-    alloc::String,
-    imports::net::{HttpMethod, Request},
+    HashMap, Result,
+    alloc::{String, Vec, string::ToString as _},
+    imports::{
+        html::Document,
+        net::{HttpMethod, Request},
+    },
     prelude::*,
 };
 
-use crate::settings;
+use core::cell::RefCell;
+
+use crate::{helpers, settings};
 
 pub struct Fetch;
 
 impl Fetch {
     pub fn request(url: String, method: HttpMethod) -> Result<Request> {
-        Ok(Request::new(url.clone(), method)?
-            .header("Referer", &format!("{}/", settings::get_base_url())))
+        let user_agent = settings::get_user_agent();
+        let cookie = settings::get_cookie();
+
+        let mut request = Request::new(url.clone(), method)?
+            .header("User-Agent", &user_agent)
+            .header("Referer", &format!("{}/", settings::get_base_url()));
+
+        if !cookie.is_empty() {
+            request = request.header("Cookie", &cookie);
+        }
+
+        Ok(request)
     }
 
     pub fn get(url: String) -> Result<Request> {
         Fetch::request(url, HttpMethod::Get)
     }
+
+    /// 取得 HTML，目前網域失效時回報可改用的網域
+    ///
+    /// `unreachable` 為失效網域 => 錯誤訊息，同一個網域只找一次可用網域
+    pub fn html(url: String, unreachable: &RefCell<HashMap<String, String>>) -> Result<Document> {
+        let base_url = settings::get_base_url();
+
+        match Fetch::get(url.clone())?.send() {
+            Ok(response) if !Fetch::is_dead(response.status_code()) => Ok(response.get_html()?),
+            _ if url.starts_with(&base_url) => {
+                let message = unreachable
+                    .borrow_mut()
+                    .entry(base_url.clone())
+                    .or_insert_with(|| Fetch::unreachable_message(&base_url))
+                    .clone();
+
+                bail!("{}", message)
+            }
+            Ok(response) => Ok(response.get_html()?),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// 從發佈頁取得網域並保存，回傳新發現的網域
+    pub fn discover_mirrors() -> Vec<String> {
+        let text = Fetch::get(settings::PUBLISHER_URL.to_string())
+            .ok()
+            .and_then(|request| request.string().ok())
+            .unwrap_or_default();
+
+        settings::add_mirrors(helpers::extract_domains(&text))
+    }
+
+    /// 連線失敗、5xx 或 403（被封鎖）才視為網域失效，404 等錯誤照常交給呼叫端
+    fn is_dead(status_code: i32) -> bool {
+        status_code == 403 || status_code >= 500
+    }
+
+    /// 網域失效時的錯誤訊息，列出目前連得上的已知網域讓使用者自行改用
+    ///
+    /// 已知網域為 source.json 的 `urls` 加上發佈頁找到的網域
+    fn unreachable_message(base_url: &str) -> String {
+        Fetch::discover_mirrors();
+
+        let mut mirrors: Vec<String> = Vec::new();
+
+        for mirror in settings::get_source_urls()
+            .into_iter()
+            .chain(settings::get_mirrors())
+        {
+            if mirror != base_url && !mirrors.contains(&mirror) {
+                mirrors.push(mirror);
+            }
+        }
+
+        let requests: Vec<Request> = mirrors
+            .iter()
+            .filter_map(|mirror| Fetch::get(format!("{}/", mirror)).ok())
+            .collect();
+
+        let working: Vec<&str> = mirrors
+            .iter()
+            .zip(Request::send_all(requests))
+            .filter(|(_, response)| {
+                response
+                    .as_ref()
+                    .is_ok_and(|response| !Fetch::is_dead(response.status_code()))
+            })
+            .map(|(mirror, _)| mirror.as_str())
+            .collect();
+
+        if working.is_empty() {
+            format!("{} 無法連線，請稍後再試", base_url)
+        } else {
+            format!(
+                "{} 無法連線，可在設定的「自訂網域」改用：{}",
+                base_url,
+                working.join("、")
+            )
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use aidoku::{
+    alloc::{String, Vec},
     canvas::Rect,
     imports::{
         canvas::{Canvas, ImageRef},
@@ -8,7 +9,6 @@ use aidoku::{
 };
 
 use md5::{Digest, Md5};
-use num_traits::float::FloatCore;
 
 pub fn get_image_pieces_num(aid_str: &str, image_id: &str) -> u32 {
     let combined = format!("{}{}", aid_str, image_id);
@@ -50,25 +50,39 @@ pub fn get_image_pieces_num(aid_str: &str, image_id: &str) -> u32 {
     }
 }
 
-pub fn reload_image(image: &ImageRef, pieces: u32) -> ImageRef {
-    let width = image.width();
-    let height = image.height() as f32;
-
-    let mut canvas = Canvas::new(width, height as f32);
+/// 依網站 JS 的切片算法計算每一片的 `(來源 y, 目的 y, 高度)`，全程使用整數避免出現縫隙
+///
+/// 原圖被切成 `pieces` 片後上下顛倒排列，除不盡的餘數併入第一片
+pub fn image_slices(height: u32, pieces: u32) -> Vec<(u32, u32, u32)> {
+    let slice_height = height / pieces;
+    let remainder = height % pieces;
+
+    (0..pieces)
+        .map(|i| {
+            let src_y = height - slice_height * (i + 1) - remainder;
+
+            if i == 0 {
+                (src_y, 0, slice_height + remainder)
+            } else {
+                (src_y, slice_height * i + remainder, slice_height)
+            }
+        })
+        .collect()
+}
 
-    let remainder = height % (pieces as f32);
+/// 還原被切片的圖片，不需要或無法還原時回傳 `None`，由呼叫端沿用原圖
+pub fn reload_image(image: &ImageRef, pieces: u32) -> Option<ImageRef> {
+    let width = image.width();
+    let height = image.height() as u32;
 
-    for i in 0..pieces {
-        let mut slice_height = FloatCore::floor(height / (pieces as f32));
-        let mut dst_y = slice_height * (i as f32);
-        let src_y = height - slice_height * ((i + 1) as f32) - remainder;
+    // 沒有切片資訊（0）、只有一片，或圖片尺寸不足以切片
+    if pieces <= 1 || width < 1.0 || height < pieces {
+        return None;
+    }
 
-        if i == 0 {
-            slice_height += remainder;
-        } else {
-            dst_y += remainder;
-        }
+    let mut canvas = Canvas::new(width, height as f32);
 
+    for (src_y, dst_y, slice_height) in image_slices(height, pieces) {
         canvas.copy_image(
             image,
             Rect::new(0.0, src_y as f32, width, slice_height as f32),
@@ -76,7 +90,7 @@ pub fn reload_image(image: &ImageRef, pieces: u32) -> ImageRef {
         );
     }
 
-    canvas.get_image()
+    Some(canvas.get_image())
 }
 
 pub fn get_current_day_of_week() -> i64 {
@@ -86,3 +100,58 @@ pub fn get_current_day_of_week() -> i64 {
     let day_of_week = (days_since_epoch + 3) % 7 + 1; // 1 = Monday, 7 = Sunday
     day_of_week
 }
+
+/// 從發佈頁內容找出禁漫網域，例如 `18comic.vip`、`https://jmcomic-zzz.one`
+pub fn extract_domains(text: &str) -> Vec<String> {
+    const KEYWORDS: &[&str] = &["18comic", "jmcomic", "jm18c"];
+
+    let mut domains: Vec<String> = Vec::new();
+
+    let tokens = text.split(|c: char| {
+        !(c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == ':' || c == '/')
+    });
+
+    for token in tokens {
+        let host = token
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .trim_matches(|c: char| c == '.' || c == '-')
+            .to_ascii_lowercase();
+
+        // 排除發佈頁本身、圖片 CDN 以及不完整的字串
+        if !host.contains('.')
+            || host.contains(':')
+            || host.starts_with("cdn")
+            || host.starts_with("jmcomicne")
+            || !KEYWORDS.iter().any(|keyword| host.contains(keyword))
+        {
+            continue;
+        }
+
+        let url = format!("https://{}", host);
+
+        if !domains.contains(&url) {
+            domains.push(url);
+        }
+    }
+
+    domains
+}
+
+/// 將禁漫圖片 CDN（`cdn-msp*`）的主機換成 `host`，其他網址保持不變
+pub fn replace_image_host(url: &str, host: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return String::from(url);
+    };
+
+    let (current_host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+    if host.is_empty() || !current_host.starts_with("cdn-msp") {
+        return String::from(url);
+    }
+
+    format!("{}://{}{}", scheme, host, path)
+}
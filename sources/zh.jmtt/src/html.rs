@@ -6,7 +6,50 @@ use aidoku::{
     prelude::*,
 };
 
-use crate::{helpers::get_image_pieces_num, url::Url};
+use crate::{helpers::get_image_pieces_num, settings, url::Url};
+
+/// 依 `ul.pagination` 判斷是否還有下一頁
+///
+/// 比較 `li.active` 與最大頁碼，頁數多時中間會省略，最後一頁以
+/// `select.pagination-select` 為準；找不到頁碼時看有沒有「»」連結
+fn has_next_page(document: &Document) -> bool {
+    let Some(items) = document.select("ul.pagination > li") else {
+        return false;
+    };
+
+    let mut current = 0;
+    let mut last = 0;
+    let mut has_next_link = false;
+
+    for item in items {
+        let text = item.text().unwrap_or_default();
+        let text = text.trim();
+
+        if let Ok(page) = text.parse::<i32>() {
+            if item.attr("class").unwrap_or_default().contains("active") {
+                current = page;
+            }
+
+            last = last.max(page);
+        } else if text == "»" && item.select_first("a[href]").is_some() {
+            has_next_link = true;
+        }
+    }
+
+    if let Some(options) = document.select("select.pagination-select > option") {
+        for option in options {
+            if let Ok(page) = option.text().unwrap_or_default().trim().parse::<i32>() {
+                last = last.max(page);
+            }
+        }
+    }
+
+    if current > 0 && last > 0 {
+        current < last
+    } else {
+        has_next_link
+    }
+}
 
 pub trait GenManga {
     fn list(&self) -> Result<MangaPageResult>;
@@ -18,6 +61,10 @@ pub trait GenManga {
 impl GenManga for Document {
     fn list(&self) -> Result<MangaPageResult> {
         let mut mangas: Vec<Manga> = Vec::new();
+        // 過濾前的數量，整頁都被封鎖時仍要能翻到下一頁
+        let mut found = 0;
+
+        let blocked_tags = settings::get_blocked_tags();
 
         let items = self
             .select("div.p-b-15")
@@ -59,18 +106,36 @@ impl GenManga for Document {
                 .trim()
                 .to_string();
 
+            let tags = item
+                .select(".tags > a.tag")
+                .map(|list| {
+                    list.map(|element| element.text().unwrap_or_default().trim().to_string())
+                        .filter(|tag| !tag.is_empty())
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default();
+
+            found += 1;
+
+            if !settings::find_blocked_tags(&tags, &blocked_tags).is_empty() {
+                continue;
+            }
+
             mangas.push(Manga {
                 key: id,
                 cover: Some(cover),
                 title,
                 url: Some(url),
+                tags: Some(tags).filter(|tags| !tags.is_empty()),
                 ..Default::default()
             });
         }
 
+        let has_next_page = found > 0 && has_next_page(self);
+
         Ok(MangaPageResult {
-            entries: mangas.clone(),
-            has_next_page: !mangas.is_empty() && self.select_first(".pagination").is_some(),
+            entries: mangas,
+            has_next_page,
         })
     }
 
@@ -9,20 +9,36 @@ mod settings;
 mod url;
 
 use aidoku::{
-    BaseUrlProvider, Chapter, FilterValue, ImageRequestProvider, ImageResponse, Listing,
+    BaseUrlProvider, Chapter, FilterValue, HashMap, ImageRequestProvider, ImageResponse, Listing,
     ListingProvider, Manga, MangaPageResult, Page, PageContext, PageImageProcessor, Result, Source,
     alloc::{String, Vec, string::ToString as _, vec},
     imports::{canvas::ImageRef, net::Request},
     prelude::*,
 };
 
+use core::cell::RefCell;
+
 use crate::{fetch::Fetch, helpers::reload_image, html::GenManga, url::Url};
 
-struct Jmtt;
+/// 封鎖標籤過濾後，單頁少於此數量就往後補頁
+const MIN_PAGE_SIZE: usize = 20;
+
+/// 單次最多往後補幾頁
+const MAX_BACKFILL_PAGES: i32 = 3;
+
+struct Jmtt {
+    /// 列表第一頁網址 => (上次回傳的頁碼, 實際讀到的網站頁碼)
+    last_pages: RefCell<HashMap<String, (i32, i32)>>,
+    /// 失效網域 => 列出可用網域的錯誤訊息
+    unreachable: RefCell<HashMap<String, String>>,
+}
 
 impl Source for Jmtt {
     fn new() -> Self {
-        Self
+        Self {
+            last_pages: RefCell::new(HashMap::new()),
+            unreachable: RefCell::new(HashMap::new()),
+        }
     }
 
     fn get_search_manga_list(
@@ -31,11 +47,9 @@ impl Source for Jmtt {
         page: i32,
         filters: Vec<FilterValue>,
     ) -> Result<MangaPageResult> {
-        let url = Url::filters(query.as_deref(), page, &filters)?.to_string();
-
-        let response = Fetch::get(url)?.html()?;
-
-        GenManga::list(&response)
+        self.list_page(page, |page| {
+            Ok(Url::filters(query.as_deref(), page, &filters)?.to_string())
+        })
     }
 
     fn get_manga_update(
@@ -46,10 +60,23 @@ impl Source for Jmtt {
     ) -> Result<Manga> {
         let url = Url::book(manga.key.clone())?.to_string();
 
-        let response = Fetch::get(url)?.html()?;
+        let response = Fetch::html(url, &self.unreachable)?;
 
         if needs_details {
             GenManga::detail(&response, &mut manga)?;
+
+            let blocked_tags = settings::find_blocked_tags(
+                manga.tags.as_deref().unwrap_or_default(),
+                &settings::get_blocked_tags(),
+            );
+
+            if !blocked_tags.is_empty() {
+                manga.description = Some(format!(
+                    "【已封鎖標籤：{}】\n{}",
+                    blocked_tags.join("、"),
+                    manga.description.unwrap_or_default()
+                ));
+            }
         }
 
         if needs_chapters {
@@ -61,7 +88,7 @@ impl Source for Jmtt {
 
     fn get_page_list(&self, _: Manga, chapter: Chapter) -> Result<Vec<Page>> {
         let url = Url::chapter(chapter.key.clone())?.to_string();
-        let response = Fetch::get(url)?.html()?;
+        let response = Fetch::html(url, &self.unreachable)?;
 
         GenManga::chapter(&response, &chapter)
     }
@@ -83,19 +110,82 @@ impl PageImageProcessor for Jmtt {
             .and_then(|ctx| ctx.get("pieces").and_then(|v| v.parse().ok()))
             .unwrap_or(0);
 
-        Ok(reload_image(&response.image, pieces))
+        // 未切片的圖片（含 GIF 等非 WebP 圖片）不帶 pieces，直接沿用原圖
+        Ok(reload_image(&response.image, pieces).unwrap_or(response.image))
     }
 }
 
 impl ImageRequestProvider for Jmtt {
     fn get_image_request(&self, url: String, _context: Option<PageContext>) -> Result<Request> {
+        let url = helpers::replace_image_host(&url, &settings::get_image_cdn());
+
         Ok(Fetch::get(url)?)
     }
 }
 
-impl ListingProvider for Jmtt {
-    fn get_manga_list(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
-        let url = match listing.id.as_str() {
+impl Jmtt {
+    /// 取得列表頁，封鎖標籤過濾後數量太少時往後補頁
+    fn list_page(
+        &self,
+        page: i32,
+        url_for: impl Fn(i32) -> Result<String>,
+    ) -> Result<MangaPageResult> {
+        let filtering = !settings::get_blocked_tags().is_empty();
+
+        self.backfill(url_for(1)?, page, filtering, |site_page| {
+            Fetch::html(url_for(site_page)?, &self.unreachable)?.list()
+        })
+    }
+
+    /// 讀取 `page` 對應的網站頁，`filtering` 時數量太少就往後補頁
+    ///
+    /// 補頁後記住讀到的網站頁碼，接續的下一頁從那之後開始，避免重複。
+    /// 偏移只在依序翻頁時成立；直接請求不接續的頁碼時無從得知先前補了幾頁，
+    /// 改從同一頁碼的網站頁開始。
+    fn backfill(
+        &self,
+        key: String,
+        page: i32,
+        filtering: bool,
+        fetch: impl Fn(i32) -> Result<MangaPageResult>,
+    ) -> Result<MangaPageResult> {
+        let mut last_pages = self.last_pages.borrow_mut();
+
+        let mut site_page = match last_pages.get(&key) {
+            Some(&(last_page, last_site_page)) if last_page + 1 == page => last_site_page + 1,
+            _ => page,
+        };
+
+        let mut result = fetch(site_page)?;
+
+        let mut backfilled = 0;
+
+        while filtering
+            && result.entries.len() < MIN_PAGE_SIZE
+            && result.has_next_page
+            && backfilled < MAX_BACKFILL_PAGES
+        {
+            site_page += 1;
+            backfilled += 1;
+
+            let next = fetch(site_page)?;
+
+            for manga in next.entries {
+                if !result.entries.iter().any(|entry| entry.key == manga.key) {
+                    result.entries.push(manga);
+                }
+            }
+
+            result.has_next_page = next.has_next_page;
+        }
+
+        last_pages.insert(key, (page, site_page));
+
+        Ok(result)
+    }
+
+    fn listing_url(id: &str, page: i32) -> Result<String> {
+        let url = match id {
             "dailymanga" => {
                 Url::serialization(helpers::get_current_day_of_week().to_string())?.to_string()
             }
@@ -203,9 +293,13 @@ impl ListingProvider for Jmtt {
             _ => bail!("Invalid listing"),
         };
 
-        let response = Fetch::get(url)?.html()?;
+        Ok(url)
+    }
+}
 
-        GenManga::list(&response)
+impl ListingProvider for Jmtt {
+    fn get_manga_list(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
+        self.list_page(page, |page| Jmtt::listing_url(&listing.id, page))
     }
 }
 
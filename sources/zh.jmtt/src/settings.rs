@@ -1,12 +1,38 @@
 use aidoku::{
-    alloc::string::String,
+    alloc::{String, Vec, string::ToString as _},
     imports::defaults::{DefaultValue, defaults_get, defaults_set},
+    prelude::*,
 };
 
 const BASE_URL_KEY: &str = "url";
-// const USER_AGENT_KEY: &str = "userAgent";
+const CUSTOM_BASE_URL_KEY: &str = "customBaseUrl";
+const MIRRORS_KEY: &str = "mirrors";
+const BLOCKED_TAGS_KEY: &str = "blockedTags";
+const USER_AGENT_TYPE_KEY: &str = "userAgentType";
+const USER_AGENT_KEY: &str = "userAgent";
+const IMAGE_CDN_KEY: &str = "imageCdn";
+const COOKIE_KEY: &str = "cookie";
+
+const DESKTOP_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/135.0.0.0 Safari/537.36";
+const MOBILE_USER_AGENT: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 18_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.4 Mobile/15E148 Safari/604.1";
+
+/// 發佈頁，列出目前可用的網域
+pub const PUBLISHER_URL: &str = "https://jmcomicne.net";
+
+const SOURCE_JSON: &str = include_str!("../res/source.json");
 
 pub fn get_base_url() -> String {
+    let custom_base_url = defaults_get::<String>(CUSTOM_BASE_URL_KEY).unwrap_or_default();
+    let custom_base_url = custom_base_url.trim().trim_end_matches('/');
+
+    if !custom_base_url.is_empty() {
+        return if custom_base_url.contains("://") {
+            custom_base_url.to_string()
+        } else {
+            format!("https://{}", custom_base_url)
+        };
+    }
+
     let mut base_url = defaults_get::<String>(BASE_URL_KEY).unwrap_or_default();
 
     if base_url.is_empty() {
@@ -21,16 +47,95 @@ pub fn get_base_url() -> String {
     base_url
 }
 
-// pub fn get_user_agent() -> String {
-//     let mut user_agent = defaults_get::<String>(USER_AGENT_KEY).unwrap_or_default();
+/// source.json 內建的網域
+pub fn get_source_urls() -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(SOURCE_JSON) else {
+        return Vec::new();
+    };
+
+    json["info"]["urls"]
+        .as_array()
+        .map(|urls| {
+            urls.iter()
+                .filter_map(|url| url.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 發佈頁找到的網域
+pub fn get_mirrors() -> Vec<String> {
+    defaults_get::<Vec<String>>(MIRRORS_KEY).unwrap_or_default()
+}
 
-//     if user_agent.is_empty() {
-//         let default_user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/135.0.0.0 Safari/537.36";
+/// 儲存發佈頁找到的網域，回傳新增的部分
+pub fn add_mirrors(urls: Vec<String>) -> Vec<String> {
+    let mut saved = get_mirrors();
+    let mut added = Vec::new();
 
-//         defaults_set(USER_AGENT_KEY, DefaultValue::String(String::from(default_user_agent)));
+    for url in urls {
+        if saved.contains(&url) {
+            continue;
+        }
 
-//         user_agent = String::from(default_user_agent);
-//     }
+        saved.push(url.clone());
+        added.push(url);
+    }
 
-//     user_agent
-// }
+    if !added.is_empty() {
+        defaults_set(MIRRORS_KEY, DefaultValue::StringArray(saved));
+    }
+
+    added
+}
+
+pub fn get_blocked_tags() -> Vec<String> {
+    defaults_get::<Vec<String>>(BLOCKED_TAGS_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// 回傳 `tags` 中被封鎖的標籤
+pub fn find_blocked_tags(tags: &[String], blocked_tags: &[String]) -> Vec<String> {
+    tags.iter()
+        .filter(|tag| blocked_tags.contains(&tag.trim().to_lowercase()))
+        .cloned()
+        .collect()
+}
+
+pub fn get_user_agent() -> String {
+    let user_agent_type = defaults_get::<String>(USER_AGENT_TYPE_KEY).unwrap_or_default();
+
+    match user_agent_type.as_str() {
+        "mobile" => String::from(MOBILE_USER_AGENT),
+        "custom" => {
+            let user_agent = defaults_get::<String>(USER_AGENT_KEY).unwrap_or_default();
+
+            if user_agent.trim().is_empty() {
+                String::from(DESKTOP_USER_AGENT)
+            } else {
+                user_agent.trim().to_string()
+            }
+        }
+        _ => String::from(DESKTOP_USER_AGENT),
+    }
+}
+
+/// 圖片 CDN 主機，空字串代表沿用網頁給的網址
+pub fn get_image_cdn() -> String {
+    defaults_get::<String>(IMAGE_CDN_KEY)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+pub fn get_cookie() -> String {
+    defaults_get::<String>(COOKIE_KEY)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
@@ -1,8 +1,14 @@
-// #![expect(clippy::unwrap_used)]
+#![expect(clippy::unwrap_used)]
 
-// use super::*;
+use super::*;
 // use aidoku::ListingKind;
-// use aidoku_test::aidoku_test;
+use aidoku::imports::{
+    defaults::{DefaultValue, defaults_set},
+    html::Html,
+};
+use aidoku_test::aidoku_test;
+
+use crate::helpers::{extract_domains, get_image_pieces_num, image_slices};
 
 // #[aidoku_test]
 // fn test_get_search_manga_list() {
@@ -151,3 +157,252 @@
 
 //     panic!("✅ 成功解析 Home: {:#?}", result);
 // }
+
+#[aidoku_test]
+fn test_list_has_next_page() {
+    // 手寫的範例，仿照 /albums 分頁與下拉選單的版面：共 856 頁的第 1、2、856 頁，
+    // 分頁連結中間省略，最後一頁只出現在下拉選單
+    let pages = [
+        (
+            r#"<div class="p-b-15"><div class="thumb-overlay-albums"><a href="/album/1111972/"><img class="lazy_img img-responsive" data-original="https://cdn-msp.18comic.vip/media/albums/1111972_3x4.jpg" src="/static/resources/images/blank.jpg"></a></div><span class="video-title title-truncate">[Pixiv] 作品集</span><div class="tags"><a class="tag" href="/search/photos?search_query=全彩">全彩</a></div></div>
+            <ul class="pagination"><li class="active"><span>1</span></li><li><a href="/albums?o=mr&page=2">2</a></li><li><a href="/albums?o=mr&page=3">3</a></li><li class="disabled"><span>...</span></li><li><a class="prevnext" href="/albums?o=mr&page=2">»</a></li></ul>
+            <select class="pagination-select"><option value="/albums?o=mr&page=1" selected>1</option><option value="/albums?o=mr&page=2">2</option><option value="/albums?o=mr&page=856">856</option></select>"#,
+            true,
+        ),
+        (
+            r#"<div class="p-b-15"><div class="thumb-overlay-albums"><a href="/album/1111972/"><img class="lazy_img img-responsive" data-original="https://cdn-msp.18comic.vip/media/albums/1111972_3x4.jpg" src="/static/resources/images/blank.jpg"></a></div><span class="video-title title-truncate">[Pixiv] 作品集</span><div class="tags"><a class="tag" href="/search/photos?search_query=全彩">全彩</a></div></div>
+            <ul class="pagination"><li><a class="prevnext" href="/albums?o=mr&page=1">«</a></li><li><a href="/albums?o=mr&page=1">1</a></li><li class="active"><span>2</span></li><li><a href="/albums?o=mr&page=3">3</a></li><li class="disabled"><span>...</span></li><li><a class="prevnext" href="/albums?o=mr&page=3">»</a></li></ul>
+            <select class="pagination-select"><option value="/albums?o=mr&page=1">1</option><option value="/albums?o=mr&page=2" selected>2</option><option value="/albums?o=mr&page=856">856</option></select>"#,
+            true,
+        ),
+        (
+            r#"<div class="p-b-15"><div class="thumb-overlay-albums"><a href="/album/1111972/"><img class="lazy_img img-responsive" data-original="https://cdn-msp.18comic.vip/media/albums/1111972_3x4.jpg" src="/static/resources/images/blank.jpg"></a></div><span class="video-title title-truncate">[Pixiv] 作品集</span><div class="tags"><a class="tag" href="/search/photos?search_query=全彩">全彩</a></div></div>
+            <ul class="pagination"><li><a class="prevnext" href="/albums?o=mr&page=855">«</a></li><li class="disabled"><span>...</span></li><li><a href="/albums?o=mr&page=855">855</a></li><li class="active"><span>856</span></li></ul>
+            <select class="pagination-select"><option value="/albums?o=mr&page=1">1</option><option value="/albums?o=mr&page=855">855</option><option value="/albums?o=mr&page=856" selected>856</option></select>"#,
+            false,
+        ),
+    ];
+
+    for (page, has_next_page) in pages {
+        let document = Html::parse(page).unwrap();
+
+        assert_eq!(document.list().unwrap().has_next_page, has_next_page);
+    }
+}
+
+#[aidoku_test]
+fn test_image_slices_match_site_js() {
+    // 期望值依網站 scramble_image 的 drawImage(img, 0, w, l, c, 0, g, l, c) 逐片算出
+    let cases: [(&str, &str, u32, &[(u32, u32, u32)]); 4] = [
+        // md5 尾字 '9'，aid >= 421926 取 % 8 => 4 片
+        (
+            "1216233",
+            "00005",
+            1001,
+            &[
+                (750, 0, 251),
+                (500, 251, 250),
+                (250, 501, 250),
+                (0, 751, 250),
+            ],
+        ),
+        // md5 尾字 '2' => 6 片
+        (
+            "1111972",
+            "00001",
+            1003,
+            &[
+                (835, 0, 168),
+                (668, 168, 167),
+                (501, 335, 167),
+                (334, 502, 167),
+                (167, 669, 167),
+                (0, 836, 167),
+            ],
+        ),
+        // md5 尾字 'd'，268850 <= aid <= 421925 取 % 10 => 2 片
+        ("350000", "00001", 1001, &[(500, 0, 501), (0, 501, 500)]),
+        // aid < 268850 固定 10 片
+        (
+            "220980",
+            "00003",
+            1003,
+            &[
+                (900, 0, 103),
+                (800, 103, 100),
+                (700, 203, 100),
+                (600, 303, 100),
+                (500, 403, 100),
+                (400, 503, 100),
+                (300, 603, 100),
+                (200, 703, 100),
+                (100, 803, 100),
+                (0, 903, 100),
+            ],
+        ),
+    ];
+
+    for (aid, page, height, expected) in cases {
+        let pieces = get_image_pieces_num(aid, page);
+
+        assert_eq!(pieces as usize, expected.len(), "aid={} page={}", aid, page);
+        assert_eq!(
+            image_slices(height, pieces),
+            expected,
+            "aid={} page={}",
+            aid,
+            page
+        );
+    }
+}
+
+#[aidoku_test]
+fn test_image_slices_cover_every_row() {
+    for pieces in [2, 4, 6, 8, 10, 12, 14, 16, 18, 20] {
+        for height in [pieces, 97, 1000, 1001, 1279, 3333] {
+            let mut rows = vec![0; height as usize];
+
+            for (src_y, dst_y, slice_height) in image_slices(height, pieces) {
+                assert!(src_y + slice_height <= height);
+
+                for row in dst_y..dst_y + slice_height {
+                    rows[row as usize] += 1;
+                }
+            }
+
+            assert!(
+                rows.iter().all(|count| *count == 1),
+                "height={} pieces={}",
+                height,
+                pieces
+            );
+        }
+    }
+}
+
+#[aidoku_test]
+fn test_image_slices_small_heights() {
+    // `reload_image` 依序把來源 y 的 `高度` 列複製到目的 y
+
+    // 高 5、2 片：第一片帶餘數共 3 列，取自打亂圖的第 3 ~ 5 列
+    assert_eq!(image_slices(5, 2), [(2, 0, 3), (0, 3, 2)]);
+
+    // 高 7、4 片：每片 1 列，第一片帶餘數共 4 列
+    assert_eq!(
+        image_slices(7, 4),
+        [(3, 0, 4), (2, 4, 1), (1, 5, 1), (0, 6, 1)]
+    );
+
+    // 高度等於片數時每片 1 列，整張上下顛倒
+    assert_eq!(image_slices(3, 3), [(2, 0, 1), (1, 1, 1), (0, 2, 1)]);
+}
+
+#[aidoku_test]
+fn test_extract_domains() {
+    let text = r#"<div class="wrap">
+        <p>最新網址：<a href="https://18comic.vip">18comic.vip</a></p>
+        <p>備用網址：jmcomic-zzz.one、JM18C-TDC.CLUB/</p>
+        <p>圖片伺服器 https://cdn-msp.18comic.vip/media/</p>
+        <p>發佈頁 jmcomicne.net，請收藏 https://18comic.vip/ 與 http://jm18c-uoi.net/album/</p>
+        <p>其他網站 example.com</p>
+    </div>"#;
+
+    assert_eq!(
+        extract_domains(text),
+        vec![
+            "https://18comic.vip",
+            "https://jmcomic-zzz.one",
+            "https://jm18c-tdc.club",
+            "https://jm18c-uoi.net",
+        ]
+    );
+}
+
+#[aidoku_test]
+fn test_source_urls() {
+    let urls = settings::get_source_urls();
+
+    assert_eq!(
+        urls.first().map(String::as_str),
+        Some("https://18comic.vip")
+    );
+    assert!(urls.iter().all(|url| url.starts_with("https://")));
+}
+
+#[aidoku_test]
+fn test_find_blocked_tags() {
+    let tags = ["獵奇", " Yaoi ", "全彩"].map(String::from);
+    let blocked = ["獵奇", "yaoi"].map(String::from);
+
+    assert_eq!(
+        settings::find_blocked_tags(&tags, &blocked),
+        ["獵奇", " Yaoi "]
+    );
+    assert!(settings::find_blocked_tags(&tags, &[]).is_empty());
+    assert!(settings::find_blocked_tags(&tags, &["全".to_string()]).is_empty());
+}
+
+#[aidoku_test]
+fn test_list_keeps_paging_when_all_blocked() {
+    let page = r#"<div class="p-b-15"><div class="thumb-overlay-albums"><a href="/album/1111972/"><img class="lazy_img img-responsive" data-original="https://cdn-msp.18comic.vip/media/albums/1111972_3x4.jpg" src="/static/resources/images/blank.jpg"></a></div><span class="video-title title-truncate">A</span><div class="tags"><a class="tag" href="/search/photos?search_query=獵奇">獵奇</a></div></div>
+        <ul class="pagination"><li class="active"><span>1</span></li><li><a href="/albums?o=mr&page=2">2</a></li><li><a class="prevnext" href="/albums?o=mr&page=2">»</a></li></ul>"#;
+
+    defaults_set(
+        "blockedTags",
+        DefaultValue::StringArray(vec!["獵奇".to_string()]),
+    );
+
+    let result = Html::parse(page).unwrap().list().unwrap();
+
+    defaults_set("blockedTags", DefaultValue::StringArray(Vec::new()));
+
+    assert!(result.entries.is_empty());
+    assert!(result.has_next_page);
+}
+
+#[aidoku_test]
+fn test_backfill_offsets() {
+    use core::cell::RefCell;
+
+    // 每個網站頁過濾後只剩 5 本，共 6 頁
+    let fetched = RefCell::new(Vec::new());
+    let fetch = |site_page: i32| -> Result<MangaPageResult> {
+        fetched.borrow_mut().push(site_page);
+
+        Ok(MangaPageResult {
+            entries: (0..5)
+                .map(|idx| Manga {
+                    key: format!("{}-{}", site_page, idx),
+                    ..Default::default()
+                })
+                .collect(),
+            has_next_page: site_page < 6,
+        })
+    };
+
+    let source = Jmtt::new();
+    let key = || "list".to_string();
+
+    // 第 1 頁讀到網站第 4 頁才滿 20 本
+    let result = source.backfill(key(), 1, true, fetch).unwrap();
+    assert_eq!(result.entries.len(), 20);
+    assert!(result.has_next_page);
+    assert_eq!(fetched.take(), [1, 2, 3, 4]);
+
+    // 第 2 頁接續網站第 5 頁，最後一頁之後不再補頁
+    let result = source.backfill(key(), 2, true, fetch).unwrap();
+    assert_eq!(result.entries.len(), 10);
+    assert!(!result.has_next_page);
+    assert_eq!(fetched.take(), [5, 6]);
+
+    // 不接續的頁碼從同一頁碼的網站頁開始
+    source.backfill(key(), 5, true, fetch).unwrap();
+    assert_eq!(fetched.take(), [5, 6]);
+
+    // 沒有封鎖標籤時不補頁
+    let result = source
+        .backfill("other".to_string(), 1, false, fetch)
+        .unwrap();
+    assert_eq!(result.entries.len(), 5);
+    assert_eq!(fetched.take(), [1]);
+}
@@ -1,104 +1,93 @@
 use aidoku::{
     Home, HomeComponent, HomeComponentValue, HomeLayout, HomePartialResult, Listing, ListingKind,
     Result,
-    alloc::{Vec, string::ToString as _, vec},
+    alloc::{String, Vec, string::ToString as _},
     imports::std::send_partial_result,
 };
 
-use crate::{Mxs, fetch::Fetch, html::GenManga, url::Url};
+use crate::{
+    Mxs,
+    fetch::Fetch,
+    html::{GenManga, RANK_BOARDS},
+    url::{self, Url},
+};
+
+/// 完結榜以排行列表顯示並連到榜單，其餘榜單以自動捲動的大卷軸顯示
+fn is_ranking(id: &str) -> bool {
+    id == "finishmanga"
+}
+
+/// 排行頁的榜單與 source.json 中對應列表的名稱
+fn rank_boards() -> Vec<(&'static str, String)> {
+    let listings = url::listings();
+
+    RANK_BOARDS
+        .iter()
+        .map(|(id, _)| {
+            let name = listings
+                .iter()
+                .find(|(listing, _)| listing == id)
+                .map_or_else(|| id.to_string(), |(_, name)| name.clone());
+
+            (*id, name)
+        })
+        .collect()
+}
 
 impl Home for Mxs {
     fn get_home(&self) -> Result<HomeLayout> {
         send_partial_result(&HomePartialResult::Layout(HomeLayout {
-            components: vec![
-                HomeComponent {
-                    title: Some("今日更新".to_string()),
-                    subtitle: None,
-                    value: HomeComponentValue::empty_big_scroller(),
-                },
-                HomeComponent {
-                    title: Some("人氣榜".to_string()),
-                    subtitle: None,
-                    value: HomeComponentValue::empty_big_scroller(),
-                },
-                HomeComponent {
-                    title: Some("推薦榜".to_string()),
-                    subtitle: None,
-                    value: HomeComponentValue::empty_big_scroller(),
-                },
-                HomeComponent {
-                    title: Some("完結榜".to_string()),
+            components: rank_boards()
+                .into_iter()
+                .map(|(id, name)| HomeComponent {
+                    title: Some(name),
                     subtitle: None,
-                    value: HomeComponentValue::empty_manga_list(),
-                },
-            ],
+                    value: if is_ranking(id) {
+                        HomeComponentValue::empty_manga_list()
+                    } else {
+                        HomeComponentValue::empty_big_scroller()
+                    },
+                })
+                .collect(),
         }));
 
         let request = Fetch::get(Url::rank(1)?.to_string())?.html()?;
 
-        let binding = GenManga::home(&request)?;
-
-        let mut chunks = binding.into_iter();
-
-        let dailymanga = chunks.next().unwrap_or_default();
-        let popularitymanga = chunks.next().unwrap_or_default();
-        let finishmanga = chunks.next().unwrap_or_default();
-        let recommendmanga = chunks.next().unwrap_or_default();
+        let mut boards = GenManga::rank_boards(&request)?;
 
         let mut components = Vec::new();
 
-        if !dailymanga.is_empty() {
-            components.push(HomeComponent {
-                title: Some("今日更新".to_string()),
-                subtitle: None,
-                value: HomeComponentValue::BigScroller {
-                    entries: dailymanga.into_iter().map(|manga| manga.into()).collect(),
-                    auto_scroll_interval: Some(8.0),
-                },
-            });
-        }
+        // 依固定順序顯示，不受排行頁區塊順序影響
+        for (id, name) in rank_boards() {
+            let Some(idx) = boards.iter().position(|(board, _)| *board == id) else {
+                continue;
+            };
 
-        if !popularitymanga.is_empty() {
-            components.push(HomeComponent {
-                title: Some("人氣榜".to_string()),
-                subtitle: None,
-                value: HomeComponentValue::BigScroller {
-                    entries: popularitymanga
-                        .into_iter()
-                        .map(|manga| manga.into())
-                        .collect(),
-                    auto_scroll_interval: Some(8.0),
-                },
-            });
-        }
+            let (_, entries) = boards.swap_remove(idx);
 
-        if !recommendmanga.is_empty() {
-            components.push(HomeComponent {
-                title: Some("推薦榜".to_string()),
-                subtitle: None,
-                value: HomeComponentValue::BigScroller {
-                    entries: recommendmanga
-                        .into_iter()
-                        .map(|manga| manga.into())
-                        .collect(),
-                    auto_scroll_interval: Some(8.0),
-                },
-            });
-        }
+            if entries.is_empty() {
+                continue;
+            }
 
-        if !finishmanga.is_empty() {
             components.push(HomeComponent {
-                title: Some("完結榜".to_string()),
+                title: Some(name.clone()),
                 subtitle: None,
-                value: HomeComponentValue::MangaList {
-                    ranking: true,
-                    page_size: Some(3),
-                    entries: finishmanga.into_iter().map(|manga| manga.into()).collect(),
-                    listing: Some(Listing {
-                        id: "finishmanga".to_string(),
-                        name: "完結榜".to_string(),
-                        kind: ListingKind::Default,
-                    }),
+                value: if is_ranking(id) {
+                    HomeComponentValue::MangaList {
+                        ranking: true,
+                        page_size: Some(3),
+                        entries: entries.into_iter().map(|manga| manga.into()).collect(),
+                        listing: Some(Listing {
+                            id: id.to_string(),
+                            name,
+                            kind: ListingKind::Default,
+                        }),
+                    }
+                } else {
+                    HomeComponentValue::BigScroller {
+                        entries,
+                        auto_scroll_interval: Some(8.0),
+                    }
                 },
             });
         }
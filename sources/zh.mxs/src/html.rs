@@ -1,18 +1,151 @@
 use aidoku::{
     Chapter, Manga, MangaPageResult, MangaStatus, Page, PageContent, Result, Viewer,
     alloc::{String, Vec, string::ToString as _},
-    imports::html::Document,
+    imports::html::{Document, Element},
     prelude::*,
 };
 
-use crate::url::Url;
+use crate::{
+    settings,
+    url::{self, Hosts, Link, Url},
+};
+
+/// 依標籤判斷閱讀方式，無法判斷時回傳 `None`
+pub fn viewer_from_tags(tags: &[String]) -> Option<Viewer> {
+    let has = |keywords: &[&str]| {
+        tags.iter()
+            .any(|tag| keywords.iter().any(|keyword| tag.contains(keyword)))
+    };
+
+    if has(&["韩漫", "韓漫", "韩国", "韓國", "条漫", "條漫"]) {
+        Some(Viewer::Webtoon)
+    } else if has(&["日漫", "日本"]) {
+        Some(Viewer::RightToLeft)
+    } else if has(&["国漫", "國漫", "大陆", "大陸"]) {
+        Some(Viewer::Webtoon)
+    } else if has(&["港台", "欧美", "歐美"]) {
+        Some(Viewer::LeftToRight)
+    } else {
+        None
+    }
+}
+
+/// 詳情頁的標籤連結
+const TAG_SELECTOR: &str = ".banner_detail_form>.info>p:nth-child(5)>span>a";
+
+/// 閱讀頁頂部的麵包屑，最後一個書籍連結是目前章節所屬的書
+const BREADCRUMB_SELECTOR: &str = ".breadcrumb a, .header .title > a, div.title > a";
+
+/// 排行頁各區塊的標題，作品標題是 `h2.title`，不會選到
+const RANK_HEADER_SELECTOR: &str = "div.title";
+
+/// 列表與首頁卡片上的作者連結，沒有時作者欄位留空
+const AUTHOR_SELECTOR: &str = ".zl > span:nth-child(2) > a, .author > span:nth-child(2) > a";
+
+/// 拆分作者欄位，例如 `作者：A&amp;B` => `["A", "B"]`
+pub fn split_authors(text: &str) -> Vec<String> {
+    let text = text.replace("&amp;", "&");
+    let text = text.trim();
+    let text = text
+        .strip_prefix("作者：")
+        .or_else(|| text.strip_prefix("作者:"))
+        .unwrap_or(text);
+
+    let mut authors: Vec<String> = Vec::new();
+
+    for name in text.split(['&', '＆', '、', ',', '，', '/', '／']) {
+        let name = name.trim().to_string();
+
+        if !name.is_empty() && !authors.contains(&name) {
+            authors.push(name);
+        }
+    }
+
+    authors
+}
+
+/// 作者搜尋的結果是否為該作者的作品，沒有作者資訊時無法確認，保留
+pub fn is_by_author(manga: &Manga, author: &str) -> bool {
+    let author = author.trim().to_lowercase();
+
+    match manga.authors.as_deref() {
+        None | Some([]) => true,
+        Some(authors) => authors
+            .iter()
+            .any(|name| name.trim().to_lowercase() == author),
+    }
+}
+
+/// 排行頁的榜單：(listing id, 標題關鍵字)，名稱以 source.json 的列表為準
+pub const RANK_BOARDS: &[(&str, &[&str])] = &[
+    ("todaymanga", &["今日更新"]),
+    ("popularitymanga", &["人气", "人氣"]),
+    ("recommendmanga", &["推荐", "推薦"]),
+    ("finishmanga", &["完结", "完結"]),
+];
+
+/// 依區塊標題找出對應的榜單 listing id
+fn rank_board_of(heading: &str) -> Option<&'static str> {
+    RANK_BOARDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|keyword| heading.contains(keyword)))
+        .map(|(id, _)| *id)
+}
+
+/// 依 `.page-pagination` 判斷是否還有下一頁
+///
+/// 比較 `a.active` 與最大頁碼（含「尾页」連結的 `page=`），
+/// 找不到頁碼時看有沒有「下一页」連結
+fn has_next_page(document: &Document) -> bool {
+    let Some(items) = document.select(".page-pagination a") else {
+        return false;
+    };
+
+    let mut current = 0;
+    let mut last = 0;
+    let mut has_next_link = false;
+
+    for item in items {
+        let text = item.text().unwrap_or_default();
+        let text = text.trim();
+        let href = item.attr("href").unwrap_or_default();
+
+        if let Ok(page) = text.parse::<i32>() {
+            if item.attr("class").unwrap_or_default().contains("active") {
+                current = page;
+            }
+
+            last = last.max(page);
+        } else if matches!(text, "尾页" | "尾頁") {
+            if let Some(page) = href
+                .split("page=")
+                .nth(1)
+                .and_then(|page| page.split('&').next())
+                .and_then(|page| page.parse::<i32>().ok())
+            {
+                last = last.max(page);
+            }
+        } else if matches!(text, ">" | "下一页" | "下一頁") && !href.is_empty() {
+            has_next_link = true;
+        }
+    }
+
+    if current > 0 && last > 0 {
+        current < last
+    } else {
+        has_next_link
+    }
+}
 
 pub trait GenManga {
     fn list(&self) -> Result<MangaPageResult>;
     fn detail(&self, manga: &mut Manga) -> Result<()>;
+    fn authors(&self) -> Option<Vec<String>>;
     fn chapters(&self) -> Result<Vec<Chapter>>;
     fn chapter(&self) -> Result<Vec<Page>>;
-    fn home(&self) -> Result<Vec<Vec<Manga>>>;
+    fn book_key(&self) -> Option<String>;
+    fn tag_links(&self) -> Vec<(String, String)>;
+    fn rank_boards(&self) -> Result<Vec<(&'static str, Vec<Manga>)>>;
 }
 
 impl GenManga for Document {
@@ -23,16 +156,18 @@ impl GenManga for Document {
             .select(".mh-item")
             .ok_or_else(|| error!("No manga items found"))?;
 
+        let hosts = Hosts::current();
+
         for item in items {
-            let id = item
+            let href = item
                 .select_first("a")
                 .ok_or_else(|| error!("No link found"))?
                 .attr("href")
-                .ok_or_else(|| error!("No link found"))?
-                .split("/")
-                .last()
-                .unwrap_or_default()
-                .to_string();
+                .ok_or_else(|| error!("No link found"))?;
+
+            let Some(Link::Book(id)) = Link::parse(&href, &hosts) else {
+                continue;
+            };
 
             let url = Url::book(id.clone())?.to_string();
 
@@ -52,21 +187,29 @@ impl GenManga for Document {
                 .trim()
                 .to_string();
 
-            let viewer = Viewer::Webtoon;
+            let authors = item
+                .select_first(AUTHOR_SELECTOR)
+                .and_then(|element| element.text())
+                .map(|text| split_authors(&text));
+
+            let viewer = settings::get_viewer().unwrap_or(Viewer::Webtoon);
 
             mangas.push(Manga {
                 key: id,
                 cover: Some(cover),
                 title,
                 url: Some(url),
+                authors,
                 viewer,
                 ..Default::default()
             });
         }
 
+        let has_next_page = !mangas.is_empty() && has_next_page(self);
+
         Ok(MangaPageResult {
-            entries: mangas.clone(),
-            has_next_page: !mangas.is_empty(),
+            entries: mangas,
+            has_next_page,
         })
     }
 
@@ -84,17 +227,7 @@ impl GenManga for Document {
             .trim()
             .to_string();
 
-        manga.authors = self
-            .select(".banner_detail_form>.info>p:nth-child(3)")
-            .map(|list| {
-                list.text()
-                    .unwrap_or_default()
-                    .trim()
-                    .replace("作者：", "")
-                    .split("&")
-                    .map(|a| a.to_string())
-                    .collect::<Vec<String>>()
-            });
+        manga.authors = self.authors();
 
         manga.artists = Some(Vec::new());
 
@@ -102,13 +235,11 @@ impl GenManga for Document {
             .select(".banner_detail_form>.info>.content")
             .map(|list| list.text().unwrap_or_default().trim().to_string());
 
-        manga.tags = self
-            .select(".banner_detail_form>.info>p:nth-child(5)>span>a")
-            .map(|list| {
-                list.map(|element| element.text().unwrap_or_default().trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect::<Vec<String>>()
-            });
+        manga.tags = self.select(TAG_SELECTOR).map(|list| {
+            list.map(|element| element.text().unwrap_or_default().trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<String>>()
+        });
 
         manga.status = match self
             .select(".banner_detail_form>.info>p:nth-child(4)>span:nth-child(1)>span")
@@ -121,11 +252,19 @@ impl GenManga for Document {
             _ => MangaStatus::Unknown,
         };
 
-        manga.viewer = Viewer::Webtoon;
+        manga.viewer = settings::get_viewer()
+            .or_else(|| viewer_from_tags(manga.tags.as_deref().unwrap_or_default()))
+            .unwrap_or(Viewer::Webtoon);
 
         Ok(())
     }
 
+    /// 詳情頁的作者欄位
+    fn authors(&self) -> Option<Vec<String>> {
+        self.select(".banner_detail_form>.info>p:nth-child(3)")
+            .map(|list| split_authors(&list.text().unwrap_or_default()))
+    }
+
     fn chapters(&self) -> Result<Vec<Chapter>> {
         let mut chapters: Vec<Chapter> = Vec::new();
 
@@ -133,14 +272,14 @@ impl GenManga for Document {
             .select("#detail-list-select>li>a")
             .ok_or_else(|| error!("No chapter items found"))?;
 
+        let hosts = Hosts::current();
+
         for (index, item) in items.enumerate() {
             let href = item.attr("href").unwrap_or_default();
 
-            if href.is_empty() {
+            let Some(Link::Chapter(key)) = Link::parse(&href, &hosts) else {
                 continue;
-            }
-
-            let key = href.split("/").last().unwrap_or_default().to_string();
+            };
 
             let url = Url::chapter(key.clone())?.to_string();
 
@@ -187,34 +326,82 @@ impl GenManga for Document {
         Ok(pages)
     }
 
-    fn home(&self) -> Result<Vec<Vec<Manga>>> {
-        let mut categories: Vec<Vec<Manga>> = Vec::new();
+    /// 閱讀頁的「返回目錄」或麵包屑連結指向所屬的書，不看推薦等其他書的連結
+    fn book_key(&self) -> Option<String> {
+        let hosts = Hosts::current();
+
+        let book_key = |link: Element| match Link::parse(&link.attr("href")?, &hosts) {
+            Some(Link::Book(key)) => Some(key),
+            _ => None,
+        };
+
+        let back_link = self.select("a").and_then(|links| {
+            links
+                .filter(|link| {
+                    link.text()
+                        .is_some_and(|text| matches!(text.trim(), "返回目录" | "返回目錄"))
+                })
+                .find_map(book_key)
+        });
+
+        back_link.or_else(|| {
+            self.select(BREADCRUMB_SELECTOR)?
+                .filter_map(book_key)
+                .last()
+        })
+    }
+
+    /// 詳情頁標籤的名稱與站內路徑
+    fn tag_links(&self) -> Vec<(String, String)> {
+        let Some(items) = self.select(TAG_SELECTOR) else {
+            return Vec::new();
+        };
+
+        let hosts = Hosts::current();
+
+        items
+            .filter_map(|item| {
+                let name = item.text()?.trim().to_string();
+                let path = url::site_path(&item.attr("href")?, &hosts)?;
+
+                (!name.is_empty()).then_some((name, path))
+            })
+            .collect()
+    }
+
+    /// 排行頁各區塊，以標題辨識是哪個榜單，無法辨識的區塊略過
+    fn rank_boards(&self) -> Result<Vec<(&'static str, Vec<Manga>)>> {
+        let mut categories: Vec<(&'static str, Vec<Manga>)> = Vec::new();
 
         let items = self
             .select(".mh-list > li")
             .ok_or_else(|| error!("No manga items found"))?;
 
+        let hosts = Hosts::current();
+
         for item in items {
+            let Some(board) = item
+                .select_first(RANK_HEADER_SELECTOR)
+                .and_then(|heading| heading.text())
+                .and_then(|text| rank_board_of(&text))
+            else {
+                continue;
+            };
+
+            if categories.iter().any(|(id, _)| *id == board) {
+                continue;
+            }
+
             let mut mangas: Vec<Manga> = Vec::new();
 
             if let Some(nodes) = item.select(".mh-item, .mh-item-tip") {
                 for node in nodes {
                     let id_attr = node.select_first("a").and_then(|a| a.attr("href"));
 
-                    if id_attr.is_none() {
+                    let Some(Link::Book(id)) = id_attr.and_then(|href| Link::parse(&href, &hosts))
+                    else {
                         continue;
-                    }
-
-                    let id = id_attr
-                        .unwrap()
-                        .split('/')
-                        .last()
-                        .unwrap_or_default()
-                        .to_string();
-
-                    if id.is_empty() {
-                        continue;
-                    }
+                    };
 
                     let url = match Url::book(id.clone()) {
                         Ok(u) => u.to_string(),
@@ -236,24 +423,16 @@ impl GenManga for Document {
                         .to_string();
 
                     let authors = node
-                        .select_first(
-                            ".zl > span:nth-child(2) > a, .author > span:nth-child(2) > a",
-                        )
+                        .select_first(AUTHOR_SELECTOR)
                         .and_then(|element| element.text())
-                        .map(|text| {
-                            text.replace("&amp;", "&")
-                                .split('&')
-                                .map(|a| a.trim().to_string())
-                                .filter(|a| !a.is_empty())
-                                .collect::<Vec<String>>()
-                        });
+                        .map(|text| split_authors(&text));
 
                     let description = node
                         .select_first(".chapter")
                         .and_then(|element| element.text())
                         .map(|text| text.trim().to_string());
 
-                    let viewer = Viewer::Webtoon;
+                    let viewer = settings::get_viewer().unwrap_or(Viewer::Webtoon);
 
                     mangas.push(Manga {
                         key: id,
@@ -268,7 +447,7 @@ impl GenManga for Document {
                 }
             }
 
-            categories.push(mangas);
+            categories.push((board, mangas));
         }
 
         Ok(categories)
@@ -11,15 +11,67 @@ use aidoku::{
     BaseUrlProvider, Chapter, DeepLinkHandler, DeepLinkResult, FilterValue, Listing,
     ListingProvider, Manga, MangaPageResult, Page, Result, Source,
     alloc::{String, Vec, string::ToString as _, vec},
+    imports::net::Request,
     prelude::*,
 };
 
 use crate::fetch::Fetch;
 use crate::html::GenManga;
-use crate::url::Url;
+use crate::url::{Hosts, Link, Url};
+
+/// 作者搜尋每頁最多讀取幾部作品的詳情頁來確認作者
+const MAX_AUTHOR_LOOKUPS: usize = 5;
 
 struct Mxs;
 
+impl Mxs {
+    /// 以作者名稱搜尋關鍵字，只保留作者相符的作品
+    ///
+    /// 關鍵字也會比對書名，所以過濾掉作者不符的作品；搜尋結果沒有作者欄位時，
+    /// 最多讀取 `MAX_AUTHOR_LOOKUPS` 部的詳情頁，其餘無法確認的作品保留
+    fn search_author(author: &str, page: i32) -> Result<MangaPageResult> {
+        let url = Url::AuthorSearch {
+            author: author.to_string(),
+            page,
+        }
+        .to_string();
+
+        let MangaPageResult {
+            mut entries,
+            has_next_page,
+        } = GenManga::list(&Fetch::get(url)?.html()?)?;
+
+        let missing: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, manga)| manga.authors.as_ref().is_none_or(|a| a.is_empty()))
+            .map(|(idx, _)| idx)
+            .take(MAX_AUTHOR_LOOKUPS)
+            .collect();
+
+        let requests = missing
+            .iter()
+            .map(|idx| Fetch::get(Url::book(entries[*idx].key.clone())?.to_string()))
+            .collect::<Result<Vec<Request>>>()?;
+
+        if !requests.is_empty() {
+            for (idx, response) in missing.into_iter().zip(Request::send_all(requests)) {
+                entries[idx].authors = response
+                    .ok()
+                    .and_then(|response| response.get_html().ok())
+                    .and_then(|document| document.authors());
+            }
+        }
+
+        entries.retain(|manga| html::is_by_author(manga, author));
+
+        Ok(MangaPageResult {
+            entries,
+            has_next_page,
+        })
+    }
+}
+
 impl Source for Mxs {
     fn new() -> Self {
         Self
@@ -31,9 +83,13 @@ impl Source for Mxs {
         page: i32,
         filters: Vec<FilterValue>,
     ) -> Result<MangaPageResult> {
-        let url = Url::filters(query.as_deref(), page, &filters)?.to_string();
+        let url = Url::filters(query.as_deref(), page, &filters)?;
 
-        let response = Fetch::get(url)?.html()?;
+        if let Url::AuthorSearch { author, page } = &url {
+            return Mxs::search_author(author, *page);
+        }
+
+        let response = Fetch::get(url.to_string())?.html()?;
 
         GenManga::list(&response)
     }
@@ -50,6 +106,22 @@ impl Source for Mxs {
 
         if needs_details {
             GenManga::detail(&response, &mut manga)?;
+
+            // 之後點選標籤時改用網站的標籤頁
+            settings::set_tag_links(&response.tag_links());
+
+            let blocked_tags = settings::find_blocked_tags(
+                manga.tags.as_deref().unwrap_or_default(),
+                &settings::get_blocked_tags(),
+            );
+
+            if !blocked_tags.is_empty() {
+                manga.description = Some(format!(
+                    "【已封鎖標籤：{}】\n{}",
+                    blocked_tags.join("、"),
+                    manga.description.unwrap_or_default()
+                ));
+            }
         }
 
         if needs_chapters {
@@ -70,17 +142,23 @@ impl Source for Mxs {
 
 impl DeepLinkHandler for Mxs {
     fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
-        if url.contains("/book/") {
-            let key = url.split("/book/").last().unwrap_or_default().to_string();
+        // https://www.mxs13.cc/book/1148 => 1148
+        // https://www.wzdhm.cc/chapter/52752/ => 52752
+        match Link::parse(&url, &Hosts::current()) {
+            Some(Link::Book(key)) => Ok(Some(DeepLinkResult::Manga { key })),
+            Some(Link::Chapter(key)) => {
+                let url = Url::chapter(key.clone())?.to_string();
 
-            if key.is_empty() {
-                return Ok(None);
-            }
+                let response = Fetch::get(url)?.html()?;
 
-            return Ok(Some(DeepLinkResult::Manga { key }));
-        }
+                let manga_key = response
+                    .book_key()
+                    .ok_or_else(|| error!("No book found for chapter {}", key))?;
 
-        Ok(None)
+                Ok(Some(DeepLinkResult::Chapter { manga_key, key }))
+            }
+            None => Ok(None),
+        }
     }
 }
 
@@ -92,6 +170,26 @@ impl BaseUrlProvider for Mxs {
 
 impl ListingProvider for Mxs {
     fn get_manga_list(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
+        // 排行頁的榜單只有一頁
+        if html::RANK_BOARDS.iter().any(|(id, _)| *id == listing.id) {
+            if page > 1 {
+                return Ok(MangaPageResult::default());
+            }
+
+            let response = Fetch::get(Url::rank(1)?.to_string())?.html()?;
+
+            let entries = GenManga::rank_boards(&response)?
+                .into_iter()
+                .find(|(id, _)| *id == listing.id)
+                .map(|(_, entries)| entries)
+                .ok_or_else(|| error!("No rank board found: {}", listing.name))?;
+
+            return Ok(MangaPageResult {
+                entries,
+                has_next_page: false,
+            });
+        }
+
         let filters = match listing.id.as_str() {
             "dailymanga" => vec![FilterValue::Sort {
                 id: "列表".to_string(),
@@ -1,10 +1,18 @@
 use aidoku::{
-    alloc::string::String,
+    Viewer,
+    alloc::{String, Vec},
     imports::defaults::{DefaultValue, defaults_get, defaults_set},
+    prelude::*,
 };
 
 const BASE_URL_KEY: &str = "url";
+const BLOCKED_TAGS_KEY: &str = "blockedTags";
 const USER_AGENT_KEY: &str = "userAgent";
+const VIEWER_KEY: &str = "viewer";
+const TAG_LINKS_KEY: &str = "tagLinks";
+
+/// 最多記住幾個標籤連結
+const MAX_TAG_LINKS: usize = 300;
 
 pub fn get_base_url() -> String {
     let mut base_url = defaults_get::<String>(BASE_URL_KEY).unwrap_or_default();
@@ -34,3 +42,76 @@ pub fn get_user_agent() -> String {
 
     user_agent
 }
+
+pub fn get_blocked_tags() -> Vec<String> {
+    defaults_get::<Vec<String>>(BLOCKED_TAGS_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// 回傳 `tags` 中被封鎖的標籤
+pub fn find_blocked_tags(tags: &[String], blocked_tags: &[String]) -> Vec<String> {
+    tags.iter()
+        .filter(|tag| blocked_tags.contains(&tag.trim().to_lowercase()))
+        .cloned()
+        .collect()
+}
+
+/// 使用者指定的閱讀方式，`auto` 時回傳 `None` 交由標籤判斷
+pub fn get_viewer() -> Option<Viewer> {
+    match defaults_get::<String>(VIEWER_KEY)
+        .unwrap_or_default()
+        .as_str()
+    {
+        "rtl" => Some(Viewer::RightToLeft),
+        "ltr" => Some(Viewer::LeftToRight),
+        "vertical" => Some(Viewer::Vertical),
+        "webtoon" => Some(Viewer::Webtoon),
+        _ => None,
+    }
+}
+
+/// 詳情頁看過的標籤連結 `(名稱, 站內路徑)`，最新的在前
+///
+/// 點選標籤時只會帶名稱，所以在讀取詳情時記下網站的標籤頁。只有更新後讀取過
+/// 詳情的作品的標籤才有路徑，最多記住 `MAX_TAG_LINKS` 個，其餘標籤改用名稱篩選
+pub fn get_tag_links() -> Vec<(String, String)> {
+    defaults_get::<Vec<String>>(TAG_LINKS_KEY)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            entry
+                .split_once('\t')
+                .map(|(name, path)| (String::from(name), String::from(path)))
+        })
+        .collect()
+}
+
+/// 記住標籤連結，同名標籤以新的路徑為準，沒有新的標籤或路徑時不寫入
+pub fn set_tag_links(links: &[(String, String)]) {
+    let saved = get_tag_links();
+
+    if links.iter().all(|link| saved.contains(link)) {
+        return;
+    }
+
+    let mut entries = links.to_vec();
+
+    for entry in saved {
+        if !entries.iter().any(|(name, _)| *name == entry.0) {
+            entries.push(entry);
+        }
+    }
+
+    entries.truncate(MAX_TAG_LINKS);
+
+    let value = entries
+        .into_iter()
+        .map(|(name, path)| format!("{}\t{}", name, path))
+        .collect();
+
+    defaults_set(TAG_LINKS_KEY, DefaultValue::StringArray(value));
+}
@@ -1,8 +1,9 @@
-// #![expect(clippy::unwrap_used)]
+#![expect(clippy::unwrap_used)]
 
-// use super::*;
+use super::*;
 // use aidoku::Home;
-// use aidoku_test::aidoku_test;
+use aidoku::{Viewer, imports::html::Html};
+use aidoku_test::aidoku_test;
 
 // #[aidoku_test]
 // fn test_get_search_manga_list() {
@@ -105,3 +106,297 @@
 
 //     panic!("✅ 成功解析 DeepLink: {:#?}", result);
 // }
+
+#[aidoku_test]
+fn test_list_has_next_page() {
+    // 手寫的範例，仿照 /booklist 分頁的版面：共 42 頁的第 1、21、42 頁
+    let pages = [
+        (
+            r#"<ul class="mh-list col7"><li><div class="mh-item"><a href="/book/52752" title="戀愛禁止"><p class="mh-cover" style="background-image: url(https://img.mxs10.cc/upload/cover/52752.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/book/52752" title="戀愛禁止">戀愛禁止</a></h2></div></div></li></ul>
+            <div class="page-pagination mt20"><div><ul><li><a href="/booklist?page=1" class="active">1</a></li><li><a href="/booklist?page=2">2</a></li><li><a href="/booklist?page=3">3</a></li><li><a href="/booklist?page=2">下一页</a></li><li><a href="/booklist?page=42">尾页</a></li></ul></div></div>"#,
+            true,
+        ),
+        (
+            r#"<ul class="mh-list col7"><li><div class="mh-item"><a href="/book/52752" title="戀愛禁止"><p class="mh-cover" style="background-image: url(https://img.mxs10.cc/upload/cover/52752.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/book/52752" title="戀愛禁止">戀愛禁止</a></h2></div></div></li></ul>
+            <div class="page-pagination mt20"><div><ul><li><a href="/booklist?page=1">首页</a></li><li><a href="/booklist?page=20">上一页</a></li><li><a href="/booklist?page=20">20</a></li><li><a href="/booklist?page=21" class="active">21</a></li><li><a href="/booklist?page=22">22</a></li><li><a href="/booklist?page=22">下一页</a></li><li><a href="/booklist?page=42">尾页</a></li></ul></div></div>"#,
+            true,
+        ),
+        (
+            r#"<ul class="mh-list col7"><li><div class="mh-item"><a href="/book/52752" title="戀愛禁止"><p class="mh-cover" style="background-image: url(https://img.mxs10.cc/upload/cover/52752.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/book/52752" title="戀愛禁止">戀愛禁止</a></h2></div></div></li></ul>
+            <div class="page-pagination mt20"><div><ul><li><a href="/booklist?page=1">首页</a></li><li><a href="/booklist?page=41">上一页</a></li><li><a href="/booklist?page=41">41</a></li><li><a href="/booklist?page=42" class="active">42</a></li><li><a href="/booklist?page=42">尾页</a></li></ul></div></div>"#,
+            false,
+        ),
+    ];
+
+    for (page, has_next_page) in pages {
+        let document = Html::parse(page).unwrap();
+
+        assert_eq!(document.list().unwrap().has_next_page, has_next_page);
+    }
+}
+
+#[aidoku_test]
+fn test_viewer_from_tags() {
+    let tags = |tags: &[&str]| {
+        tags.iter()
+            .map(|tag| tag.to_string())
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        html::viewer_from_tags(&tags(&["韩国", "恋爱"])),
+        Some(Viewer::Webtoon)
+    );
+    assert_eq!(
+        html::viewer_from_tags(&tags(&["大陆"])),
+        Some(Viewer::Webtoon)
+    );
+    assert_eq!(
+        html::viewer_from_tags(&tags(&["日本", "后宫"])),
+        Some(Viewer::RightToLeft)
+    );
+    assert_eq!(
+        html::viewer_from_tags(&tags(&["港台"])),
+        Some(Viewer::LeftToRight)
+    );
+    assert_eq!(html::viewer_from_tags(&tags(&["恋爱"])), None);
+}
+
+#[aidoku_test]
+fn test_link_parse() {
+    use crate::url::{Hosts, Link};
+
+    let hosts = Hosts::current();
+    let parse = |url: &str| Link::parse(url, &hosts);
+
+    let book = |id: &str| Some(Link::Book(id.to_string()));
+    let chapter = |id: &str| Some(Link::Chapter(id.to_string()));
+
+    assert_eq!(parse("https://www.mxs13.cc/book/1148"), book("1148"));
+    assert_eq!(parse("https://www.jjmhw2.top/book/1148/"), book("1148"));
+    assert_eq!(
+        parse("https://WZDHM.cc/book/1148?from=share#top"),
+        book("1148")
+    );
+    assert_eq!(parse("https://www.wzd1.cc/chapter/52752"), chapter("52752"));
+    assert_eq!(
+        parse("https://www.mxs19.cc/chapter/52752/?page=2"),
+        chapter("52752")
+    );
+    assert_eq!(parse("/chapter/52752.html"), chapter("52752"));
+    assert_eq!(parse("/book/1148"), book("1148"));
+
+    assert_eq!(parse("https://www.dm5.com/book/1148"), None);
+    assert_eq!(parse("https://www.mxs13.cc/booklist?tag=全部"), None);
+    assert_eq!(parse("https://www.mxs13.cc/book/"), None);
+    assert_eq!(parse("https://www.mxs13.cc/book/1148/comments"), None);
+
+    let source = Mxs::new();
+    let result = source
+        .handle_deep_link("https://www.wzdhm2.cc/book/1148/".to_string())
+        .unwrap();
+    assert!(matches!(result, Some(DeepLinkResult::Manga { ref key }) if key == "1148"));
+}
+
+#[aidoku_test]
+fn test_chapter_book_key() {
+    // 推薦區的書在麵包屑之前，不能當成所屬的書
+    let document = Html::parse(
+        r#"<div class="mh-item"><a href="/book/2000">推薦</a></div>
+        <div class="header"><a href="/">首页</a><a href="/booklist">漫画</a></div>
+        <div class="title"><a href="/booklist">漫画</a> / <a href="https://www.mxs13.cc/book/1148">華爾街夜色</a> / 第1話</div>"#,
+    )
+    .unwrap();
+    assert_eq!(document.book_key(), Some("1148".to_string()));
+
+    let document =
+        Html::parse(r#"<a href="/book/2000">推薦</a><a href="/book/1148/"> 返回目录 </a>"#)
+            .unwrap();
+    assert_eq!(document.book_key(), Some("1148".to_string()));
+
+    let document =
+        Html::parse(r#"<a href="/booklist">漫画</a><a href="/book/2000">推薦</a>"#).unwrap();
+    assert_eq!(document.book_key(), None);
+}
+
+#[aidoku_test]
+fn test_split_authors() {
+    assert_eq!(html::split_authors("作者：韩国&amp;Yuri"), ["韩国", "Yuri"]);
+    assert_eq!(html::split_authors(" 作者:A & B、A "), ["A", "B"]);
+    assert_eq!(html::split_authors("金作家"), ["金作家"]);
+    assert!(html::split_authors("作者：").is_empty());
+
+    let manga = |authors: Option<&[&str]>| Manga {
+        authors: authors.map(|authors| authors.iter().map(|a| a.to_string()).collect()),
+        ..Default::default()
+    };
+
+    assert!(html::is_by_author(
+        &manga(Some(&["Yuri", "韩国"])),
+        " yuri "
+    ));
+    assert!(!html::is_by_author(&manga(Some(&["Yuri Kim"])), "Yuri"));
+    assert!(html::is_by_author(&manga(None), "Yuri"));
+    assert!(html::is_by_author(&manga(Some(&[])), "Yuri"));
+
+    let url = Url::filters(
+        None,
+        2,
+        &[FilterValue::Text {
+            id: "author".to_string(),
+            value: " 金作家 ".to_string(),
+        }],
+    )
+    .unwrap();
+
+    assert!(matches!(url, Url::AuthorSearch { ref author, page: 2 } if author == "金作家"));
+    assert!(url.to_string().contains("/search?keyword="));
+    assert!(url.to_string().ends_with("&page=2"));
+}
+
+#[aidoku_test]
+fn test_tag_links() {
+    let document = Html::parse(
+        r#"<div class="banner_detail_form"><div class="info">
+            <h1>A</h1>
+            <p class="subtitle">别名：A</p>
+            <p class="subtitle">作者：B</p>
+            <p class="tip"><span class="block">状态：<span>连载中</span></span></p>
+            <p class="tip"><span class="block">
+                <a href="/tag/qingchun">青春</a>
+                <a href="https://www.mxs13.cc/booklist?tag=%E6%81%8B%E7%88%B1">恋爱</a>
+                <a href="https://www.example.com/tag/x">外站</a>
+                <a href="/tag/empty"> </a>
+            </span></p>
+        </div></div>"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        document.tag_links(),
+        [
+            ("青春".to_string(), "/tag/qingchun".to_string()),
+            (
+                "恋爱".to_string(),
+                "/booklist?tag=%E6%81%8B%E7%88%B1".to_string()
+            ),
+        ]
+    );
+
+    settings::set_tag_links(&document.tag_links());
+
+    let genre = |value: &str| {
+        Url::filters(
+            None,
+            2,
+            &[FilterValue::Select {
+                id: "genre".to_string(),
+                value: value.to_string(),
+            }],
+        )
+        .unwrap()
+        .to_string()
+    };
+
+    assert!(genre("青春").ends_with("/tag/qingchun?page=2"));
+    assert!(genre("恋爱").ends_with("/booklist?tag=%E6%81%8B%E7%88%B1&page=2"));
+    assert!(genre("长腿").contains("/booklist?tag=长腿&area=-1&end=-1&page=2"));
+}
+
+#[aidoku_test]
+fn test_tag_page() {
+    let tag = |path: &str, page: i32| {
+        Url::Tag {
+            path: path.to_string(),
+            page,
+        }
+        .to_string()
+    };
+
+    assert!(tag("/tag/qingchun", 1).ends_with("/tag/qingchun?page=1"));
+    assert!(tag("/tag/qingchun?page=3", 2).ends_with("/tag/qingchun?page=2"));
+    assert!(tag("/booklist?page=3&tag=%E9%9D%92", 2).ends_with("/booklist?tag=%E9%9D%92&page=2"));
+
+    let page = |active: i32| {
+        let links = (1..=3)
+            .map(|page| {
+                let class = if page == active {
+                    r#" class="active""#
+                } else {
+                    ""
+                };
+
+                format!(r#"<li><a href="/tag/qingchun?page={page}"{class}>{page}</a></li>"#)
+            })
+            .collect::<String>();
+
+        format!(
+            r#"<ul class="mh-list"><li><div class="mh-item"><a href="/book/1148"><p class="mh-cover" style="background-image: url(https://example.com/1148.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/book/1148">A</a></h2></div></div></li></ul>
+            <div class="page-pagination"><ul>{links}<li><a href="/tag/qingchun?page=3">尾页</a></li></ul></div>"#
+        )
+    };
+
+    for (active, has_next_page) in [(1, true), (2, true), (3, false)] {
+        let document = Html::parse(page(active)).unwrap();
+
+        assert_eq!(document.list().unwrap().has_next_page, has_next_page);
+    }
+}
+
+#[aidoku_test]
+fn test_rank_boards() {
+    let item = |id: &str, title: &str| {
+        format!(
+            r#"<div class="mh-item"><a href="/book/{id}"><p class="mh-cover" style="background-image: url(https://example.com/{id}.jpg)"></p></a><div class="mh-item-detali"><h2 class="title"><a href="/book/{id}">{title}</a></h2></div></div>"#
+        )
+    };
+
+    // 區塊順序與首頁不同，另有無法辨識與沒有標題的區塊
+    let html = format!(
+        r#"<ul class="mh-list">
+            <li><div class="title"><h4>完结榜</h4></div>{}</li>
+            <li><div class="title"><h4>热门标签</h4></div>{}</li>
+            <li><div class="title"><h4>人气榜</h4></div>{}{}</li>
+            <li><div class="title"><h4>今日更新</h4></div>{}</li>
+            <li>{}</li>
+        </ul>"#,
+        item("1", "A"),
+        item("2", "推荐的书"),
+        item("3", "C"),
+        item("4", "D"),
+        item("5", "E"),
+        item("6", "推荐的书"),
+    );
+
+    let boards = Html::parse(html).unwrap().rank_boards().unwrap();
+
+    let keys = boards
+        .iter()
+        .map(|(id, entries)| {
+            let keys = entries.iter().map(|m| m.key.as_str()).collect::<Vec<_>>();
+
+            (*id, keys)
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        keys,
+        [
+            ("finishmanga", vec!["1"]),
+            ("popularitymanga", vec!["3", "4"]),
+            ("todaymanga", vec!["5"]),
+        ]
+    );
+}
+
+#[aidoku_test]
+fn test_rank_board_listings() {
+    let listings = url::listings();
+
+    // 首頁標題取自 source.json，每個榜單都要有對應的列表
+    for (id, _) in html::RANK_BOARDS {
+        assert!(listings.iter().any(|(listing, _)| listing == id), "{}", id);
+    }
+
+    assert!(listings.contains(&("todaymanga".to_string(), "今日更新榜".to_string())));
+}
@@ -1,12 +1,167 @@
 use aidoku::{
     FilterValue, Result,
-    alloc::{String, string::ToString as _},
+    alloc::{String, Vec, string::ToString as _},
     helpers::uri::encode_uri,
     prelude::*,
 };
 
 use crate::settings;
 
+/// source.json 列出的鏡像網址，同一本書在各鏡像的 id 一致
+const SOURCE_JSON: &str = include_str!("../res/source.json");
+
+/// 站內網域：source.json 的所有鏡像與目前設定的網址
+///
+/// 解析整頁連結前建立一次，不必每個連結都讀取設定
+pub struct Hosts {
+    base_host: String,
+    mirror_hosts: Vec<String>,
+}
+
+impl Hosts {
+    pub fn current() -> Self {
+        Self {
+            base_host: normalize_host(host_of(&settings::get_base_url())),
+            mirror_hosts: mirror_urls()
+                .iter()
+                .map(|url| normalize_host(host_of(url)))
+                .collect(),
+        }
+    }
+
+    /// 鏡像網域或目前設定的網址，不分大小寫並忽略 `www.` 與連接埠
+    fn contains(&self, host: &str) -> bool {
+        let host = normalize_host(host);
+
+        host == self.base_host || self.mirror_hosts.contains(&host)
+    }
+}
+
+/// source.json `urls` 陣列中的網址
+fn mirror_urls() -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(SOURCE_JSON) else {
+        return Vec::new();
+    };
+
+    json["info"]["urls"]
+        .as_array()
+        .map(|urls| {
+            urls.iter()
+                .filter_map(|url| url.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// source.json `listings` 陣列中的 `(id, 名稱)`
+pub fn listings() -> Vec<(String, String)> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(SOURCE_JSON) else {
+        return Vec::new();
+    };
+
+    json["listings"]
+        .as_array()
+        .map(|listings| {
+            listings
+                .iter()
+                .filter_map(|listing| {
+                    Some((
+                        String::from(listing["id"].as_str()?),
+                        String::from(listing["name"].as_str()?),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `https://www.mxs13.cc/book/1148` => `www.mxs13.cc`
+fn host_of(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+
+    rest.split('/').next().unwrap_or_default()
+}
+
+/// `WWW.MXS13.cc:443` => `mxs13.cc`
+fn normalize_host(host: &str) -> String {
+    let host = host
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    host.trim_start_matches("www.").to_string()
+}
+
+/// 站內連結指向的書籍或章節
+#[derive(Debug, PartialEq)]
+pub enum Link {
+    Book(String),
+    Chapter(String),
+}
+
+impl Link {
+    /// 解析完整網址或站內路徑，例如 `https://www.mxs13.cc/book/1148/?from=share` => `Book("1148")`
+    pub fn parse(url: &str, hosts: &Hosts) -> Option<Self> {
+        let path = match url.split_once("://") {
+            Some((_, rest)) => {
+                let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+                if !hosts.contains(host) {
+                    return None;
+                }
+
+                path
+            }
+            None => url,
+        };
+
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+
+        let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+
+        let kind = segments.next()?;
+        let id = segments.next()?.trim_end_matches(".html");
+
+        if segments.next().is_some() || id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        match kind {
+            "book" => Some(Self::Book(id.to_string())),
+            "chapter" => Some(Self::Chapter(id.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// 站內連結的路徑（含查詢字串），例如 `https://www.mxs13.cc/tag/abc` => `/tag/abc`，外站連結回傳 `None`
+pub fn site_path(href: &str, hosts: &Hosts) -> Option<String> {
+    let href = href.trim();
+
+    let path = match href.split_once("://") {
+        Some((_, rest)) => {
+            let (host, path) = rest.split_once('/')?;
+
+            if !hosts.contains(host) {
+                return None;
+            }
+
+            path
+        }
+        None => href,
+    };
+
+    let path = path
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('/');
+
+    (!path.is_empty()).then(|| format!("/{}", path))
+}
+
 #[derive(Clone)]
 pub enum Url {
     Filter {
@@ -19,6 +174,11 @@ pub enum Url {
         query: String,
         page: i32,
     },
+    /// 以作者名稱做關鍵字搜尋，`page` 為搜尋結果的頁碼
+    AuthorSearch {
+        author: String,
+        page: i32,
+    },
     Chapter {
         id: String,
     },
@@ -26,6 +186,11 @@ pub enum Url {
         list_type: String,
         page: i32,
     },
+    /// 詳情頁標籤連結指向的列表頁，`path` 可帶查詢字串
+    Tag {
+        path: String,
+        page: i32,
+    },
     Book {
         id: String,
     },
@@ -45,9 +210,33 @@ impl Url {
             Self::Search { query, page } => {
                 format!("{}/search?keyword={}&page={}", base_url, query, page)
             }
+            Self::AuthorSearch { author, page } => {
+                format!(
+                    "{}/search?keyword={}&page={}",
+                    base_url,
+                    encode_uri(author),
+                    page
+                )
+            }
             Self::ListType { list_type, page } => {
                 format!("{}/{}?page={}", base_url, list_type, page)
             }
+            Self::Tag { path, page } => {
+                // 標籤連結本身可能帶有頁碼，以要讀取的頁碼取代
+                let (path, query) = path.split_once('?').unwrap_or((path, ""));
+
+                let mut query = query
+                    .split('&')
+                    .filter(|param| !param.is_empty() && !param.starts_with("page="))
+                    .collect::<Vec<_>>()
+                    .join("&");
+
+                if !query.is_empty() {
+                    query.push('&');
+                }
+
+                format!("{}{}?{}page={}", base_url, path, query, page)
+            }
             Self::Filter {
                 tag,
                 area,
@@ -76,6 +265,12 @@ impl Url {
 
         for filter in filters {
             match filter {
+                FilterValue::Text { id, value } if id == "author" => {
+                    return Ok(Self::AuthorSearch {
+                        author: value.trim().to_string(),
+                        page,
+                    });
+                }
                 FilterValue::Text { value, .. } => {
                     return Ok(Self::Search {
                         query: encode_uri(value.clone()),
@@ -100,7 +295,17 @@ impl Url {
                     "题材" => tag = value.clone(),
                     "地区" => area = value.clone(),
                     "进度" => end = value.clone(),
-                    "genre" => tag = value.clone(),
+                    "genre" => {
+                        // 看過的標籤直接使用網站的標籤頁，沒看過的以名稱篩選
+                        if let Some((_, path)) = settings::get_tag_links()
+                            .into_iter()
+                            .find(|(name, _)| name == value)
+                        {
+                            return Ok(Self::Tag { path, page });
+                        }
+
+                        tag = value.clone();
+                    }
                     _ => continue,
                 },
                 _ => continue,
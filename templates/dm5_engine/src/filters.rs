@@ -0,0 +1,108 @@
+use aidoku::{
+    alloc::{String, string::ToString as _},
+    prelude::*,
+};
+
+/// 一種篩選條件的對照表，各陣列依相同順序排列
+pub struct FilterTable {
+    /// 組合篩選（`/manhua-list-…`）使用的內部 id，例如 `tag31`
+    pub ids: &'static [&'static str],
+    /// 單一篩選使用的網址代碼，例如 `rexue`；空陣列表示只能組合篩選
+    pub slugs: &'static [&'static str],
+    /// 簡體介面的選項名稱
+    pub hans: &'static [&'static str],
+    /// 繁體介面的選項名稱
+    pub hant: &'static [&'static str],
+}
+
+impl FilterTable {
+    /// 以選項名稱查出 id 或網址代碼，先比對目前語言，再比對另一種
+    fn convert(&self, value: &str, traditional: bool, single: bool) -> String {
+        let (first, second) = if traditional {
+            (self.hant, self.hans)
+        } else {
+            (self.hans, self.hant)
+        };
+
+        let targets = if single { self.slugs } else { self.ids };
+
+        first
+            .iter()
+            .position(|&opt| opt == value)
+            .or_else(|| second.iter().position(|&opt| opt == value))
+            .and_then(|idx| targets.get(idx))
+            .map(|&s| s.to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// 各站的篩選對照表
+pub struct FilterTables {
+    pub areas: FilterTable,
+    pub words: FilterTable,
+    pub tags: FilterTable,
+    pub audiences: FilterTable,
+    pub pays: FilterTable,
+    pub statuses: FilterTable,
+}
+
+/// 使用者選取的篩選條件（選項名稱），未選取為空字串
+#[derive(Default)]
+pub struct FilterSelection {
+    pub area: String,
+    pub word: String,
+    pub tag: String,
+    pub audience: String,
+    pub pay: String,
+    pub status: String,
+    /// 排序代碼，例如 `s2`
+    pub sort: String,
+}
+
+impl FilterTables {
+    /// `/manhua` 之後的路徑，例如 `-list-area35-tag31-s2-p2`
+    pub fn path(&self, selection: &FilterSelection, traditional: bool, page: i32) -> String {
+        // 網址中依此順序排列
+        let fields = [
+            (&self.areas, selection.area.as_str()),
+            (&self.words, selection.word.as_str()),
+            (&self.tags, selection.tag.as_str()),
+            (&self.audiences, selection.audience.as_str()),
+            (&self.pays, selection.pay.as_str()),
+            (&self.statuses, selection.status.as_str()),
+        ];
+
+        // 「全部」等同未篩選
+        let is_active = |value: &str| !value.is_empty() && value != "全部";
+
+        let active = fields.iter().filter(|(_, value)| is_active(value)).count();
+
+        // 只有一個篩選且網站有對應的代碼時，使用 `/manhua-rexue` 形式，其餘一律 `/manhua-list-…`
+        let single = active == 1
+            && fields
+                .iter()
+                .all(|(table, value)| !is_active(value) || !table.slugs.is_empty());
+
+        let mut path = String::from(if single { "" } else { "-list" });
+
+        for (table, value) in fields {
+            if !is_active(value) {
+                continue;
+            }
+
+            let id = table.convert(value, traditional, single);
+
+            if !id.is_empty() {
+                path.push_str(&format!("-{}", id));
+            }
+        }
+
+        if !selection.sort.is_empty() {
+            path.push_str(&format!("-{}", selection.sort));
+        }
+
+        path.push_str(&format!("-p{}", page));
+
+        path
+    }
+}
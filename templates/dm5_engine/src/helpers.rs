@@ -0,0 +1,707 @@
+use aidoku::{
+    HashMap, PageContext, Result, Viewer,
+    alloc::{String, Vec, string::ToString as _, vec},
+    prelude::*,
+};
+
+use crate::packer;
+
+/// 章節閱讀頁上呼叫 `chapterfun.ashx` 需要的 `DM5_*` 變數
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChapterVars {
+    pub cid: String,
+    pub mid: String,
+    pub image_count: usize,
+    pub sign: String,
+    pub dt: String,
+    pub key: String,
+}
+
+impl ChapterVars {
+    pub fn parse(html: &str) -> Result<Self> {
+        let var = |name: &str| {
+            extract_dm5_var(html, name)
+                .map(|value| value.to_string())
+                .ok_or_else(|| error!("No {}", name))
+        };
+
+        Ok(Self {
+            cid: var("DM5_CID")?,
+            mid: var("DM5_MID")?,
+            image_count: var("DM5_IMAGE_COUNT")?
+                .parse()
+                .map_err(|_| error!("Bad IMAGE_COUNT"))?,
+            sign: var("DM5_VIEWSIGN")?,
+            dt: var("DM5_VIEWSIGN_DT")?,
+            key: extract_dm5_key(html).unwrap_or_default().to_string(),
+        })
+    }
+
+    /// 第 `page` 頁的 `chapterfun.ashx` 網址
+    pub fn chapterfun_url(&self, base_url: &str, language: &str, page: usize) -> String {
+        format!(
+            "{}/chapterfun.ashx?cid={}&page={}&key={}&language={}&gtk=6&_cid={}&_mid={}&_dt={}&_sign={}",
+            base_url, self.cid, page, self.key, language, self.cid, self.mid, self.dt, self.sign
+        )
+    }
+
+    /// 存進 `PageContext`，讓 `get_image_request` 逐頁解析圖片
+    pub fn to_context(&self, page: usize) -> PageContext {
+        let mut context: PageContext = HashMap::new();
+
+        context.insert("cid".to_string(), self.cid.clone());
+        context.insert("mid".to_string(), self.mid.clone());
+        context.insert("count".to_string(), self.image_count.to_string());
+        context.insert("sign".to_string(), self.sign.clone());
+        context.insert("dt".to_string(), self.dt.clone());
+        context.insert("key".to_string(), self.key.clone());
+        context.insert("page".to_string(), page.to_string());
+
+        context
+    }
+
+    /// 從 `PageContext` 還原，回傳 (變數, 頁碼)
+    pub fn from_context(context: &PageContext) -> Option<(Self, usize)> {
+        let get = |name: &str| context.get(name).cloned();
+
+        let vars = Self {
+            cid: get("cid")?,
+            mid: get("mid")?,
+            image_count: get("count")?.parse().ok()?,
+            sign: get("sign")?,
+            dt: get("dt")?,
+            key: get("key").unwrap_or_default(),
+        };
+
+        let page = get("page")?.parse().ok()?;
+
+        Some((vars, page))
+    }
+}
+
+/// Extract image URLs from decoded `dm5imagefun` JavaScript.
+///
+/// The decoded JS looks like:
+/// ```text
+/// function dm5imagefun(){var cid=123;var key='abc';var pix="https://...";
+/// var pvalue=["/1.jpg","/2.jpg"];for(...){pvalue[i]=pix+pvalue[i]+'?cid=123&key=abc'}
+/// return pvalue}
+/// ```
+pub fn extract_image_urls(decoded: &str) -> Option<Vec<String>> {
+    let pix = find_var_string(decoded, "pix")?;
+    let cid = find_var_number(decoded, "cid")?;
+    let key = find_var_string(decoded, "key")?;
+    let pvalue = find_var_array(decoded, "pvalue")?;
+
+    let urls = pvalue
+        .iter()
+        .map(|p| format!("{}{}?cid={}&key={}", pix, p, cid, key))
+        .collect();
+
+    Some(urls)
+}
+
+/// 手機版閱讀頁的 `newImgs` 圖片陣列，通常包在 packed script 裡
+pub fn extract_mobile_images(html: &str) -> Option<Vec<String>> {
+    let packed = html
+        .match_indices("eval(function(p,a,c,k,e,")
+        .filter_map(|(pos, _)| packer::unpack(&html[pos..]).ok());
+
+    packed
+        .chain(core::iter::once(String::from(html)))
+        .find_map(|js| find_var_array(&js, "newImgs"))
+        .filter(|images| !images.is_empty())
+}
+
+// ── helpers for extracting JS variables ──────────────────────────────
+
+/// Find `var <name>="value"` or `var <name>='value'` and return `value`.
+fn find_var_string(js: &str, name: &str) -> Option<String> {
+    for sep in &["=\"", "='", "=\\'", "=\\\""] {
+        let needle = format!("{}{}", name, sep);
+        if let Some(pos) = js.find(&needle) {
+            let after = &js[pos + needle.len()..];
+            // The quote we are looking for is the last char of the separator,
+            // but if it's an escaped quote like `\'` we want to find the next `\'`.
+            // Let's just look for the first matching quote character (either ' or ").
+            let quote_char = if sep.contains('\'') { '\'' } else { '"' };
+
+            let mut end_pos = 0;
+            let bytes = after.as_bytes();
+            while end_pos < bytes.len() {
+                if bytes[end_pos] == quote_char as u8 {
+                    // check if it's escaped
+                    if end_pos > 0 && bytes[end_pos - 1] == b'\\' {
+                        // If we are matching an escaped quote pattern `=\'`, the closing quote will likely also be `\'`
+                        if sep.contains('\\') {
+                            // This is the closing escaped quote, strip the backslash from the value later
+                            break;
+                        } else {
+                            // Just an escaped quote inside a normal string, ignore it
+                            end_pos += 1;
+                            continue;
+                        }
+                    }
+                    break;
+                }
+                end_pos += 1;
+            }
+
+            if end_pos < bytes.len() {
+                let mut value = String::from(&after[..end_pos]);
+                // If it was an escaped quote string, the last character of the value might be a backslash right before the quote
+                if sep.contains('\\') && value.ends_with('\\') {
+                    value.pop();
+                }
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
+/// Find `var <name>=12345` and return the number as a string.
+fn find_var_number(js: &str, name: &str) -> Option<String> {
+    let needle = format!("{}=", name);
+    let pos = js.find(&needle)?;
+    let after = &js[pos + needle.len()..];
+    let end = after
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(after.len());
+    if end == 0 {
+        return None;
+    }
+    Some(String::from(&after[..end]))
+}
+
+/// Find `var <name>=["/a.jpg","/b.jpg"]` and return the entries.
+fn find_var_array(js: &str, name: &str) -> Option<Vec<String>> {
+    let needle = format!("{}=[", name);
+    let pos = js.find(&needle)?;
+    let after = &js[pos + needle.len()..];
+    let end = after.find(']')?;
+    let content = &after[..end];
+
+    let mut entries = Vec::new();
+    let bytes = content.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'"' || bytes[i] == b'\'' {
+            let quote = bytes[i];
+            i += 1;
+            let start = i;
+            while i < bytes.len() && bytes[i] != quote {
+                i += 1;
+            }
+            entries.push(String::from(&content[start..i]));
+            i += 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    Some(entries)
+}
+
+/// Extract a JS variable from the page HTML, e.g. `var DM5_CID=1217932;` or `var DM5_CID = 1217932;`
+pub fn extract_dm5_var<'a>(html: &'a str, var_name: &str) -> Option<&'a str> {
+    let needle = format!("var {}", var_name);
+    let mut current_html = html;
+
+    while let Some(pos) = current_html.find(&needle) {
+        let after_var = &current_html[pos + needle.len()..];
+        let next_char = after_var.chars().next().unwrap_or('\0');
+
+        if next_char == '=' || next_char.is_whitespace() {
+            if let Some(eq_pos) = after_var.find('=') {
+                let after_eq = &after_var[eq_pos + 1..];
+                if let Some(end) = after_eq.find(|c: char| c == ';' || c == '\r' || c == '\n') {
+                    let value = after_eq[..end].trim().trim_matches('"').trim_matches('\'');
+                    return Some(value);
+                }
+            }
+        }
+        current_html = &current_html[pos + needle.len()..];
+    }
+    None
+}
+
+/// Extract the `dm5_key` hidden input value from the page HTML.
+pub fn extract_dm5_key(html: &str) -> Option<&str> {
+    let needle = "id=\"dm5_key\" value=\"";
+    let pos = html.find(needle)?;
+    let after = &html[pos + needle.len()..];
+    let end = after.find('"')?;
+    Some(&after[..end])
+}
+
+/// 網址的主機是 `domains` 之一（含子網域）時，回傳路徑的第一段，
+/// 例如 `https://www.dm5.com/manhua-xxx/?from=share` => `manhua-xxx`
+pub fn link_segment<'a>(url: &'a str, domains: &[&str]) -> Option<&'a str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(end);
+    let host = host
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let is_site = domains.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain)
+                .is_some_and(|subdomain| subdomain.ends_with('.'))
+    });
+
+    if !is_site {
+        return None;
+    }
+
+    path.split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_matches('/')
+        .split('/')
+        .next()
+}
+
+/// 電腦版網址對應的手機版，例如 `https://www.dm5.cn` => `https://m.dm5.cn`
+pub fn mobile_base_url(base_url: &str) -> String {
+    let (scheme, host) = base_url
+        .trim_end_matches('/')
+        .split_once("://")
+        .unwrap_or(("https", base_url.trim_end_matches('/')));
+
+    let host = host.strip_prefix("www.").unwrap_or(host);
+
+    if host.starts_with("m.") {
+        format!("{}://{}", scheme, host)
+    } else {
+        format!("{}://m.{}", scheme, host)
+    }
+}
+
+/// 漫畫網址的路徑，例如 `/manhua-xxx/` => `manhua-xxx`，列表與排行頁除外
+pub fn manga_key_of(path: &str) -> Option<String> {
+    // 完整網址只取路徑
+    let path = path
+        .split_once("://")
+        .and_then(|(_, rest)| rest.split_once('/'))
+        .map_or(path, |(_, path)| path);
+    let key = path.trim().trim_matches('/');
+
+    let is_manga = key.starts_with("manhua-")
+        && key.len() > "manhua-".len()
+        && !key.contains('/')
+        && ![
+            "manhua-list",
+            "manhua-rank",
+            "manhua-new",
+            "manhua-jingxuan",
+        ]
+        .iter()
+        .any(|prefix| key.starts_with(prefix));
+
+    is_manga.then(|| key.to_string())
+}
+
+/// 章節網址的路徑，例如 `m1217932` 或 `m1217932-p2` => `m1217932`
+pub fn chapter_key_of(path: &str) -> Option<String> {
+    let key = path.trim().trim_matches('/');
+    let key = key.split("-p").next().unwrap_or_default();
+    let cid = key.strip_prefix('m')?;
+
+    (!cid.is_empty() && cid.chars().all(|c| c.is_ascii_digit())).then(|| key.to_string())
+}
+
+/// 從章節頁中找出所屬漫畫的連結（麵包屑或返回目錄）
+pub fn find_manga_link(html: &str) -> Option<String> {
+    html.match_indices("href=\"/manhua-").find_map(|(pos, _)| {
+        let after = &html[pos + "href=\"".len()..];
+        let end = after.find('"')?;
+
+        manga_key_of(&after[..end])
+    })
+}
+
+/// 詳情頁「相關推薦」區塊的標題
+pub const RELATED_HEADINGS: &[&str] = &["相关推荐", "相關推薦"];
+
+/// 詳情頁「同作者作品」區塊的標題
+pub const SAME_AUTHOR_HEADINGS: &[&str] = &["同作者作品"];
+
+/// 頁面上的會員編號 `DM5_USERID`，未登入時為 0 或不存在
+pub fn user_id_of(html: &str) -> Option<u64> {
+    extract_dm5_var(html, "DM5_USERID")?
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id > 0)
+}
+
+/// 付費章節的閱讀頁沒有圖片數量，只有購買表單
+pub fn is_locked_chapter(html: &str) -> bool {
+    let image_count = extract_dm5_var(html, "DM5_IMAGE_COUNT")
+        .and_then(|count| count.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    image_count == 0 && (html.contains("view-pay-form") || html.contains("chapterpay"))
+}
+
+/// 作者欄位中標示分工的前綴，例如 `原作:`、`漫畫：`
+const AUTHOR_ROLES: &[&str] = &[
+    "原作", "原案", "漫画", "漫畫", "作画", "作畫", "脚本", "腳本", "编剧", "編劇", "作者",
+];
+
+/// 出版社名稱的結尾，例如 `集英社`、`角川書店`
+const PUBLISHER_SUFFIXES: &[&str] = &["社", "书店", "書店", "出版", "文库", "文庫"];
+
+/// `作者A&作者B`、`作者A、作者B`、`作者A 作者B` => 各作者；英文名中的空白保留
+///
+/// 去掉 `原作:` 之類的分工前綴，並略過出版社
+pub fn split_authors(text: &str) -> Vec<String> {
+    const SEPARATORS: &[char] = &['&', '＆', '、', '/', '／', '×', ',', '，', ';', '；', '|'];
+
+    text.split(SEPARATORS)
+        .flat_map(|part| {
+            let part = part.trim();
+            let words: Vec<&str> = part.split_whitespace().collect();
+
+            // 只有每段都含中日文字時才以空白分隔，避免拆開 `Oda Eiichiro`
+            if words.len() > 1 && words.iter().all(|word| !word.is_ascii()) {
+                words
+            } else {
+                vec![part]
+            }
+        })
+        .map(strip_author_role)
+        .filter(|name| !name.is_empty())
+        .filter(|name| {
+            !PUBLISHER_SUFFIXES
+                .iter()
+                .any(|suffix| name.ends_with(suffix))
+        })
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn strip_author_role(name: &str) -> &str {
+    AUTHOR_ROLES
+        .iter()
+        .find_map(|role| name.strip_prefix(role)?.strip_prefix([':', '：']))
+        .unwrap_or(name)
+        .trim()
+}
+
+/// 番外、特別篇，沒有單位時取標記後的數字，例如 `番外2`、`特别篇 3`
+const EXTRA_MARKERS: &[&str] = &["番外", "特别篇", "特別篇"];
+
+const CHAPTER_UNITS: &[char] = &['话', '話', '回', '章', '集'];
+
+const VOLUME_UNITS: &[char] = &['卷', '册', '冊'];
+
+/// `第12话`、`第12.5話`、`第一百零二回`、`番外2` => 章節編號
+pub fn parse_chapter_number(title: &str) -> Option<f32> {
+    find_numbered(title, CHAPTER_UNITS).or_else(|| parse_extra_number(title))
+}
+
+/// `第3卷`、`第三冊`、`卷03` => 卷數
+pub fn parse_volume_number(title: &str) -> Option<f32> {
+    find_numbered(title, VOLUME_UNITS).or_else(|| {
+        let rest = title.trim().strip_prefix(VOLUME_UNITS)?;
+        let run: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| is_numeral(*c))
+            .collect();
+
+        parse_numeral(&run)
+    })
+}
+
+fn parse_extra_number(title: &str) -> Option<f32> {
+    EXTRA_MARKERS.iter().find_map(|marker| {
+        let rest = &title[title.find(marker)? + marker.len()..];
+        let run: String = rest
+            .trim_start_matches(['篇', ' ', '：', ':'])
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+
+        parse_numeral(&run)
+    })
+}
+
+/// 找出第一個緊接單位（可省略「第」、可有空白）的數字
+fn find_numbered(title: &str, units: &[char]) -> Option<f32> {
+    let chars: Vec<char> = title.chars().collect();
+
+    for (idx, c) in chars.iter().enumerate() {
+        if !units.contains(c) {
+            continue;
+        }
+
+        let mut end = idx;
+        while end > 0 && chars[end - 1] == ' ' {
+            end -= 1;
+        }
+
+        let mut start = end;
+        while start > 0 && is_numeral(chars[start - 1]) {
+            start -= 1;
+        }
+
+        let run: String = chars[start..end].iter().collect();
+
+        // 中文數字要緊接「第」，避免「最后一回」被當成第 1 回
+        let has_digit = run.chars().any(|c| c.is_ascii_digit());
+
+        if !has_digit && (start == 0 || chars[start - 1] != '第') {
+            continue;
+        }
+
+        if let Some(number) = parse_numeral(&run) {
+            return Some(number);
+        }
+    }
+
+    None
+}
+
+fn is_numeral(c: char) -> bool {
+    c.is_ascii_digit() || c == '.' || chinese_digit(c).is_some() || chinese_unit(c).is_some()
+}
+
+fn parse_numeral(run: &str) -> Option<f32> {
+    let run = run.trim_matches('.');
+
+    if run.is_empty() {
+        return None;
+    }
+
+    if run.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return run.parse::<f32>().ok();
+    }
+
+    parse_chinese_number(run).map(|number| number as f32)
+}
+
+fn chinese_digit(c: char) -> Option<u32> {
+    match c {
+        '零' | '〇' => Some(0),
+        '一' => Some(1),
+        '二' | '两' | '兩' => Some(2),
+        '三' => Some(3),
+        '四' => Some(4),
+        '五' => Some(5),
+        '六' => Some(6),
+        '七' => Some(7),
+        '八' => Some(8),
+        '九' => Some(9),
+        _ => None,
+    }
+}
+
+fn chinese_unit(c: char) -> Option<u32> {
+    match c {
+        '十' => Some(10),
+        '百' => Some(100),
+        '千' => Some(1000),
+        '万' | '萬' => Some(10000),
+        _ => None,
+    }
+}
+
+/// `十二` => 12、`一百零五` => 105、`两千` => 2000
+fn parse_chinese_number(s: &str) -> Option<u32> {
+    let mut total = 0;
+    let mut section = 0;
+    let mut digit = 0;
+
+    for c in s.chars() {
+        if let Some(value) = chinese_digit(c) {
+            digit = value;
+        } else if let Some(unit) = chinese_unit(c) {
+            if unit == 10000 {
+                total += (section + digit) * unit;
+                section = 0;
+            } else {
+                // 「十二」開頭省略了「一」
+                section += digit.max(1) * unit;
+            }
+            digit = 0;
+        } else {
+            return None;
+        }
+    }
+
+    Some(total + section + digit)
+}
+
+/// 網站時間為 UTC+8
+const UTC_OFFSET: i64 = 8 * 3600;
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// 文字中的一段連續數字，以及它前一個字元與後面第一個非空白字元
+struct DigitRun {
+    value: i64,
+    len: usize,
+    prev: Option<char>,
+    next: Option<char>,
+}
+
+fn digit_runs(text: &str) -> Vec<DigitRun> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut runs = Vec::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        if !chars[idx].is_ascii_digit() {
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+        let mut value: i64 = 0;
+
+        while idx < chars.len() && chars[idx].is_ascii_digit() {
+            value = value
+                .saturating_mul(10)
+                .saturating_add(chars[idx].to_digit(10).unwrap_or(0) as i64);
+            idx += 1;
+        }
+
+        runs.push(DigitRun {
+            value,
+            len: idx - start,
+            prev: start.checked_sub(1).map(|prev| chars[prev]),
+            next: chars[idx..].iter().find(|c| **c != ' ').copied(),
+        });
+    }
+
+    runs
+}
+
+/// 1970-01-01 起算的天數
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+fn year_from_days(days: i64) -> i64 {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let month = (5 * doy + 2) / 153;
+
+    yoe + era * 400 + if month >= 10 { 1 } else { 0 }
+}
+
+fn is_valid_date(month: i64, day: i64) -> bool {
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+/// `今天 10:22`、`昨天`、`11月01號`、`2023-05-01` => 時間戳，`now` 為目前時間戳
+pub fn parse_update_date(text: &str, now: i64) -> Option<i64> {
+    let today = (now + UTC_OFFSET).div_euclid(SECONDS_PER_DAY);
+    let runs = digit_runs(text);
+
+    let full_date = runs.windows(3).find_map(|w| {
+        let is_date = w[0].len == 4
+            && matches!(w[0].next, Some('-' | '/' | '年'))
+            && matches!(w[1].next, Some('-' | '/' | '月'))
+            && is_valid_date(w[1].value, w[2].value);
+
+        is_date.then(|| days_from_civil(w[0].value, w[1].value, w[2].value))
+    });
+
+    let month_day = || {
+        runs.windows(2).find_map(|w| {
+            let is_date = w[0].next == Some('月')
+                && matches!(w[1].next, Some('号' | '號' | '日'))
+                && is_valid_date(w[0].value, w[1].value);
+
+            if !is_date {
+                return None;
+            }
+
+            // 沒有年份：取不晚於今天的那一年
+            let year = year_from_days(today);
+            let days = days_from_civil(year, w[0].value, w[1].value);
+
+            Some(if days > today {
+                days_from_civil(year - 1, w[0].value, w[1].value)
+            } else {
+                days
+            })
+        })
+    };
+
+    let day = if text.contains("今天") {
+        today
+    } else if text.contains("昨天") {
+        today - 1
+    } else if text.contains("前天") {
+        today - 2
+    } else {
+        full_date.or_else(month_day)?
+    };
+
+    let time = runs
+        .windows(2)
+        .find(|w| w[0].next == Some(':') && w[0].value < 24 && w[1].value < 60)
+        .map(|w| w[0].value * 3600 + w[1].value * 60)
+        .unwrap_or(0);
+
+    Some(day * SECONDS_PER_DAY + time - UTC_OFFSET)
+}
+
+/// `第1话（20P）`、`第1话 （20 P）` => 20
+pub fn parse_page_count(text: &str) -> Option<i32> {
+    digit_runs(text)
+        .into_iter()
+        .find(|run| {
+            matches!(run.prev, Some('(' | '（' | ' '))
+                && matches!(run.next, Some('P' | 'p'))
+                && run.value > 0
+        })
+        .and_then(|run| i32::try_from(run.value).ok())
+}
+
+/// 依地區、標籤（或地區連結代碼）判斷閱讀方式，無法判斷時回傳 `None`
+pub fn detect_viewer(labels: &[String]) -> Option<Viewer> {
+    let has = |keywords: &[&str]| {
+        labels
+            .iter()
+            .any(|label| keywords.iter().any(|keyword| label.contains(keyword)))
+    };
+
+    if has(&["条漫", "條漫", "韩漫", "韓漫", "韩国", "韓國"]) {
+        Some(Viewer::Webtoon)
+    } else if has(&["日韩", "日韓", "日本", "日漫", "jpkr"]) {
+        Some(Viewer::RightToLeft)
+    } else if has(&["大陆", "大陸", "国漫", "國漫", "china"]) {
+        Some(Viewer::Webtoon)
+    } else if has(&["港台", "欧美", "歐美", "hktw", "euus"]) {
+        Some(Viewer::LeftToRight)
+    } else {
+        None
+    }
+}
+
+/// 閱讀頁的 `DM5_READMODE`：`1` 為日漫的右到左翻頁、`3` 為條漫，其他值無法判斷
+pub fn viewer_from_read_mode(html: &str) -> Option<Viewer> {
+    match extract_dm5_var(html, "DM5_READMODE")?.trim() {
+        "1" => Some(Viewer::RightToLeft),
+        "3" => Some(Viewer::Webtoon),
+        _ => None,
+    }
+}
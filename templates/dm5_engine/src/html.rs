@@ -0,0 +1,473 @@
+use aidoku::{
+    Chapter, Manga, MangaPageResult, MangaStatus, Page, PageContent, Result, Viewer,
+    alloc::{String, Vec, string::ToString as _, vec},
+    imports::{html::Document, net::Request, std::current_date},
+    prelude::*,
+};
+
+use crate::{
+    Params,
+    helpers::{self, ChapterVars},
+    packer,
+};
+
+/// 依分頁連結判斷是否還有下一頁
+///
+/// 比較 `.active` 與最大頁碼；最後一頁的「>」仍指回本頁，
+/// 只有找不到頁碼時才看「>」連結
+fn has_next_page(document: &Document, params: &Params) -> bool {
+    let Some(items) = document.select(params.selectors.pagination) else {
+        return false;
+    };
+
+    let mut current = 0;
+    let mut last = 0;
+    let mut has_next_link = false;
+
+    for item in items {
+        let text = item.text().unwrap_or_default();
+        let text = text.trim();
+
+        if let Ok(page) = text.parse::<i32>() {
+            if item.attr("class").unwrap_or_default().contains("active") {
+                current = page;
+            }
+
+            last = last.max(page);
+        } else if matches!(text, ">" | "下一页" | "下一頁")
+            && item.attr("href").is_some_and(|href| !href.is_empty())
+        {
+            has_next_link = true;
+        }
+    }
+
+    if current > 0 && last > 0 {
+        current < last
+    } else {
+        has_next_link
+    }
+}
+
+/// 一次同時送出的 chapterfun 請求數
+const CHAPTERFUN_BATCH_SIZE: usize = 20;
+
+/// 缺頁時最多重試幾輪
+const MAX_CHAPTERFUN_ROUNDS: usize = 3;
+
+pub trait GenManga {
+    fn list(&self, params: &Params) -> Result<MangaPageResult>;
+    fn detail(&self, params: &Params, manga: &mut Manga) -> Result<()>;
+    fn chapters(&self, params: &Params) -> Result<Vec<Chapter>>;
+    fn chapter(params: &Params, url: String, body: String) -> Result<Vec<Page>>;
+    fn mobile_chapter(body: String) -> Result<Vec<Page>>;
+    fn recommendations(&self, params: &Params, headings: &[&str]) -> Vec<Manga>;
+}
+
+impl GenManga for Document {
+    fn list(&self, params: &Params) -> Result<MangaPageResult> {
+        let mut mangas: Vec<Manga> = Vec::new();
+
+        let items = self
+            .select(params.selectors.list_item)
+            .ok_or_else(|| error!("No manga items found"))?;
+
+        for item in items {
+            let html_a_tag = item
+                .select_first("a")
+                .ok_or_else(|| error!("No link found"))?;
+
+            let id = html_a_tag
+                .attr("href")
+                .ok_or_else(|| error!("No href found"))?
+                .trim_matches('/')
+                .to_string();
+
+            let title = html_a_tag
+                .attr("title")
+                .ok_or_else(|| error!("No title found"))?
+                .trim()
+                .to_string();
+
+            let url = params.url(&id);
+
+            let cover = item
+                .select_first(params.selectors.list_cover)
+                .ok_or_else(|| error!("No cover found"))?
+                .attr("style")
+                .ok_or_else(|| error!("No style found"))?
+                .replace("background-image: url(", "")
+                .replace(")", "");
+
+            mangas.push(Manga {
+                key: id,
+                cover: Some(cover),
+                title,
+                url: Some(url),
+                // 列表沒有地區資訊，交給詳情頁判斷
+                viewer: params.viewer,
+                ..Default::default()
+            });
+        }
+
+        let has_next_page = !mangas.is_empty() && has_next_page(self, params);
+
+        Ok(MangaPageResult {
+            entries: mangas,
+            has_next_page,
+        })
+    }
+
+    fn detail(&self, params: &Params, manga: &mut Manga) -> Result<()> {
+        let selectors = params.selectors;
+
+        manga.cover = self
+            .select_first(selectors.detail_cover)
+            .ok_or_else(|| error!("No cover found"))?
+            .attr("src");
+
+        manga.title = self
+            .select_first(selectors.detail_title)
+            .ok_or_else(|| error!("No title found"))?
+            .own_text()
+            .ok_or_else(|| error!("No title found"))?
+            .trim()
+            .to_string();
+
+        // 每位作者一個連結，點擊後以作者模式搜尋
+        let mut authors: Vec<String> = Vec::new();
+
+        for author in self
+            .select(selectors.detail_authors)
+            .ok_or_else(|| error!("No authors found"))?
+        {
+            for name in helpers::split_authors(&author.text().unwrap_or_default()) {
+                if !authors.contains(&name) {
+                    authors.push(name);
+                }
+            }
+        }
+
+        manga.authors = Some(authors);
+
+        manga.artists = Some(Vec::new());
+
+        manga.description = self
+            .select(selectors.detail_description)
+            .map(|list| list.text().unwrap_or_default().trim().to_string());
+
+        manga.tags = self.select(selectors.detail_tags).map(|list| {
+            list.map(|element| {
+                element
+                    .select_first("span")
+                    .map_or_else(|| element.text(), |span| span.text())
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            })
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<String>>()
+        });
+
+        manga.status = match self
+            .select(selectors.detail_status)
+            .map(|list| list.text().unwrap_or_default())
+            .unwrap_or_default()
+            .trim()
+        {
+            "连载中" | "連載中" => MangaStatus::Ongoing,
+            "已完结" | "已完結" => MangaStatus::Completed,
+            _ => MangaStatus::Unknown,
+        };
+
+        // 地區、題材連結的文字與網址（如 `/manhua-jpkr/`）
+        let labels: Vec<String> = self
+            .select(selectors.detail_labels)
+            .map(|list| {
+                list.flat_map(|element| [element.text(), element.attr("href")])
+                    .flatten()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        manga.viewer = helpers::detect_viewer(&labels).unwrap_or(Viewer::Unknown);
+
+        Ok(())
+    }
+
+    fn chapters(&self, params: &Params) -> Result<Vec<Chapter>> {
+        let mut chapters: Vec<Chapter> = Vec::new();
+
+        let selectors = params.selectors;
+
+        // 分頁標籤（連載、單行本…）與章節列表依序對應
+        let tabs: Vec<String> = self
+            .select(selectors.chapter_tabs)
+            .map(|tabs| {
+                tabs.filter_map(|tab| tab.text())
+                    .map(|text| text.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // 網站只在詳情頁標示最後更新日期，個別章節沒有日期，
+        // 所以只標在連載分頁的第一項（網站預設新到舊排列），其餘章節留空
+        let mut latest_date = self
+            .select_first(selectors.chapter_date)
+            .and_then(|title| title.text())
+            .and_then(|text| helpers::parse_update_date(&text, current_date() as i64));
+
+        let lists = self
+            .select(selectors.chapter_lists)
+            .ok_or_else(|| error!("No chapter items found"))?;
+
+        for (idx, list) in lists.enumerate() {
+            let is_volume_tab = tabs
+                .get(idx)
+                .is_some_and(|tab| tab.contains("单行本") || tab.contains("單行本"));
+
+            // 只取列表的直接子項目（含 .chapteritem 內的），章節裡的巢狀 li 不算
+            let scope = match list.attr("id") {
+                Some(id) => format!("#{}", id),
+                None => selectors.chapter_lists.to_string(),
+            };
+
+            let Some(items) = self.select(&format!("{0} > li, {0} > .chapteritem > li", scope))
+            else {
+                continue;
+            };
+
+            for item in items {
+                let html_a_tag = item
+                    .select_first("a")
+                    .ok_or_else(|| error!("No link found"))?;
+
+                let key = html_a_tag
+                    .attr("href")
+                    .ok_or_else(|| error!("No href found"))?
+                    .trim_matches('/')
+                    .to_string();
+
+                let url = params.url(&key);
+
+                let locked = html_a_tag.select_first(selectors.chapter_lock).is_some();
+
+                if locked && params.hide_locked_chapters {
+                    continue;
+                }
+
+                let mut title = html_a_tag
+                    .own_text()
+                    .or_else(|| {
+                        html_a_tag
+                            .select_first(selectors.chapter_title)
+                            .and_then(|title| title.own_text())
+                    })
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+
+                let (chapter_number, volume_number) = if is_volume_tab {
+                    (None, helpers::parse_volume_number(&title))
+                } else {
+                    (
+                        helpers::parse_chapter_number(&title),
+                        helpers::parse_volume_number(&title),
+                    )
+                };
+
+                // 頁數沒有專屬欄位，附在標題後面
+                if let Some(count) = item
+                    .text()
+                    .and_then(|text| helpers::parse_page_count(&text))
+                {
+                    title.push_str(&format!("（{}P）", count));
+                }
+
+                let date_uploaded = if idx == 0 { latest_date.take() } else { None };
+
+                chapters.push(Chapter {
+                    key,
+                    title: Some(title),
+                    chapter_number,
+                    volume_number,
+                    date_uploaded,
+                    url: Some(url),
+                    locked,
+                    ..Default::default()
+                });
+            }
+        }
+
+        Ok(chapters)
+    }
+
+    fn chapter(params: &Params, url: String, body: String) -> Result<Vec<Page>> {
+        // Extract DM5 variables from the chapter page
+        let vars = ChapterVars::parse(&body)?;
+
+        // 逐頁模式：先回傳頁面，圖片網址交給 get_image_request 解析
+        if params.lazy_pages {
+            let mut pages: Vec<Page> = Vec::new();
+
+            for page in 1..=vars.image_count {
+                let page_url = params.url(&format!("m{}-p{}", vars.cid, page));
+
+                pages.push(Page {
+                    content: PageContent::url_context(page_url, vars.to_context(page)),
+                    ..Default::default()
+                });
+            }
+
+            return Ok(pages);
+        }
+
+        // 每一頁各打一次 chapterfun，只採用回傳的第一張圖，重複的圖片視為失敗並重試
+        let mut slots: Vec<Option<String>> = vec![None; vars.image_count];
+
+        for _ in 0..MAX_CHAPTERFUN_ROUNDS {
+            let missing: Vec<usize> = (1..=vars.image_count)
+                .filter(|page| slots[page - 1].is_none())
+                .collect();
+
+            if missing.is_empty() {
+                break;
+            }
+
+            for batch in missing.chunks(CHAPTERFUN_BATCH_SIZE) {
+                let requests = batch
+                    .iter()
+                    .map(|page| {
+                        Ok(params
+                            .get(params.chapterfun_url(&vars, *page))?
+                            .header("Referer", &url))
+                    })
+                    .collect::<Result<Vec<Request>>>()?;
+
+                let responses = Request::send_all(requests);
+
+                for (page, response) in batch.iter().zip(responses) {
+                    let Some(img_url) = response
+                        .ok()
+                        .and_then(|response| response.get_string().ok())
+                        .and_then(|packed| packer::unpack(&packed).ok())
+                        .and_then(|decoded| helpers::extract_image_urls(&decoded))
+                        .and_then(|urls| urls.into_iter().next())
+                    else {
+                        continue;
+                    };
+
+                    if slots.iter().flatten().any(|url| *url == img_url) {
+                        continue;
+                    }
+
+                    slots[page - 1] = Some(img_url);
+                }
+            }
+        }
+
+        let resolved = slots.iter().flatten().count();
+
+        if resolved != vars.image_count {
+            bail!(
+                "圖片數量不符：應有 {} 頁，只取得 {} 頁",
+                vars.image_count,
+                resolved
+            );
+        }
+
+        let pages = slots
+            .into_iter()
+            .flatten()
+            .map(|img_url| Page {
+                content: PageContent::url(img_url),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(pages)
+    }
+
+    fn recommendations(&self, params: &Params, headings: &[&str]) -> Vec<Manga> {
+        let mut mangas: Vec<Manga> = Vec::new();
+
+        // 標題文字符合的區塊，作品在標題的下一個元素裡
+        let Some(items) = self
+            .select(params.selectors.section_title)
+            .and_then(|mut titles| {
+                titles.find(|title| {
+                    title
+                        .text()
+                        .is_some_and(|text| headings.contains(&text.trim()))
+                })
+            })
+            .and_then(|title| title.next())
+            .and_then(|section| section.select("li"))
+        else {
+            return mangas;
+        };
+
+        for item in items {
+            let Some(link) = item.select_first("a[href]") else {
+                continue;
+            };
+
+            let Some(key) = link.attr("href").and_then(|href| (params.manga_key)(&href)) else {
+                continue;
+            };
+
+            if mangas.iter().any(|manga| manga.key == key) {
+                continue;
+            }
+
+            let title = link
+                .attr("title")
+                .or_else(|| {
+                    item.select_first(".title, h2, h3")
+                        .and_then(|element| element.text())
+                })
+                .or_else(|| link.text())
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+
+            if title.is_empty() {
+                continue;
+            }
+
+            let cover = item
+                .select_first("img")
+                .and_then(|img| img.attr("data-src").or_else(|| img.attr("src")))
+                .or_else(|| {
+                    item.select_first(".mh-cover")
+                        .and_then(|element| element.attr("style"))
+                        .map(|style| style.replace("background-image: url(", "").replace(")", ""))
+                });
+
+            mangas.push(Manga {
+                url: Some(params.url(&key)),
+                key,
+                cover,
+                title,
+                viewer: params.viewer,
+                ..Default::default()
+            });
+        }
+
+        mangas
+    }
+
+    fn mobile_chapter(body: String) -> Result<Vec<Page>> {
+        let images = helpers::extract_mobile_images(&body)
+            .ok_or_else(|| error!("No images found in mobile chapter page"))?;
+
+        let pages = images
+            .into_iter()
+            .map(|img_url| Page {
+                content: PageContent::url(img_url),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(pages)
+    }
+}
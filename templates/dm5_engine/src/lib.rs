@@ -0,0 +1,20 @@
+//! 動漫屋系網站（dm5、1kkk、manhuaren、xmanhua、mangabz）共用的解析引擎
+//!
+//! 這些網站共用 `DM5_*` 變數、`chapterfun.ashx` 的 packed script 與 `mh-list` 版型，
+//! 各站的來源只需提供網域、選擇器與篩選對照表。
+#![cfg_attr(not(test), no_std)]
+extern crate alloc;
+
+pub mod filters;
+pub mod helpers;
+pub mod html;
+pub mod packer;
+mod params;
+pub mod rank;
+mod source;
+
+pub use params::{Params, Selectors};
+pub use source::{Engine, deep_link, verify_login};
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,207 @@
+//! Dean Edwards p.a.c.k.e.r. 解包
+//!
+//! 支援的形式：
+//! ```text
+//! eval(function(p,a,c,k,e,d){...}('template',62,120,'kw1|kw2|...'.split('|'),0,{}))
+//! eval(function(p,a,c,k,e,r){...}('template',[],120,'kw1|kw2|...'.split('|')))
+//! ```
+//! 不依賴網站內容，其他使用 packed script 的來源可以直接沿用。
+
+use aidoku::alloc::{String, Vec};
+use core::fmt;
+
+/// 基數 ≤ 62 時的數字字元，與 packer 的 `e(c)` 編碼相同
+const ALPHABET_62: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// 基數 > 62 時 packer 改用 High ASCII（U+00A1 起）編碼
+const HIGH_ASCII_START: u32 = 0xA1;
+
+const MAX_BASE: usize = 95;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnpackError {
+    /// 找不到 `eval(function(p,a,c,k,e,…)` 或其參數
+    NotPacked,
+    /// 參數格式不符，附上出錯的參數名稱
+    MalformedArguments(&'static str),
+    /// 字串參數沒有結尾引號
+    UnterminatedString,
+    UnsupportedBase(usize),
+    /// `count` 與關鍵字數量不一致
+    SymtabMismatch {
+        count: usize,
+        keywords: usize,
+    },
+}
+
+impl fmt::Display for UnpackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotPacked => write!(f, "not a p.a.c.k.e.r. script"),
+            Self::MalformedArguments(name) => write!(f, "malformed packer argument `{}`", name),
+            Self::UnterminatedString => write!(f, "unterminated string in packer arguments"),
+            Self::UnsupportedBase(base) => write!(f, "unsupported packer base {}", base),
+            Self::SymtabMismatch { count, keywords } => write!(
+                f,
+                "packer symtab has {} keywords but count is {}",
+                keywords, count
+            ),
+        }
+    }
+}
+
+/// 解開 `source` 中第一段 packed script
+pub fn unpack(source: &str) -> Result<String, UnpackError> {
+    let start = find_packed(source).ok_or(UnpackError::NotPacked)?;
+    let args = payload_args(&source[start..]).ok_or(UnpackError::NotPacked)?;
+
+    let (template, rest) = read_string(args)?;
+    let rest = expect_comma(rest, "base")?;
+
+    let (base, rest) = if let Some(rest) = rest.strip_prefix("[]") {
+        // 壓縮過的 packer 會以 `[]` 代表 62
+        (62, rest)
+    } else {
+        read_number(rest).ok_or(UnpackError::MalformedArguments("base"))?
+    };
+
+    if !(2..=MAX_BASE).contains(&base) {
+        return Err(UnpackError::UnsupportedBase(base));
+    }
+
+    let rest = expect_comma(rest, "count")?;
+    let (count, rest) = read_number(rest).ok_or(UnpackError::MalformedArguments("count"))?;
+    let rest = expect_comma(rest, "keywords")?;
+    let (keywords, rest) = read_string(rest)?;
+
+    if !rest.trim_start().starts_with(".split(") {
+        return Err(UnpackError::MalformedArguments("keywords"));
+    }
+
+    let keywords: Vec<&str> = keywords.split('|').collect();
+
+    if count != keywords.len() {
+        return Err(UnpackError::SymtabMismatch {
+            count,
+            keywords: keywords.len(),
+        });
+    }
+
+    Ok(substitute(&template, base, &keywords))
+}
+
+/// `eval(function(p,a,c,k,e,d)` 或 `(p,a,c,k,e,r)` 的位置
+fn find_packed(source: &str) -> Option<usize> {
+    let pos = source.find("function(p,a,c,k,e,")?;
+
+    source[..pos].trim_end().ends_with("eval(").then_some(pos)
+}
+
+/// 函式本體之後 `}(` 的參數開頭
+fn payload_args(source: &str) -> Option<&str> {
+    let pos = ["}('", "}(\""]
+        .iter()
+        .filter_map(|needle| source.find(needle))
+        .min()?;
+
+    Some(&source[pos + 2..])
+}
+
+fn expect_comma<'a>(s: &'a str, name: &'static str) -> Result<&'a str, UnpackError> {
+    s.trim_start()
+        .strip_prefix(',')
+        .map(str::trim_start)
+        .ok_or(UnpackError::MalformedArguments(name))
+}
+
+fn read_number(s: &str) -> Option<(usize, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    Some((s[..end].parse().ok()?, &s[end..]))
+}
+
+/// 讀取 JS 字串字面值並處理跳脫字元，回傳內容與剩餘字串
+fn read_string(s: &str) -> Result<(String, &str), UnpackError> {
+    let mut chars = s.char_indices();
+
+    let quote = match chars.next() {
+        Some((_, quote @ ('\'' | '"'))) => quote,
+        _ => return Err(UnpackError::MalformedArguments("string")),
+    };
+
+    let mut value = String::new();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 'r')) => value.push('\r'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, escaped)) => value.push(escaped),
+                None => break,
+            },
+            c if c == quote => return Ok((value, &s[idx + c.len_utf8()..])),
+            c => value.push(c),
+        }
+    }
+
+    Err(UnpackError::UnterminatedString)
+}
+
+/// 以 packer 的編碼規則把字元轉回數字
+fn digit_of(c: char, base: usize) -> Option<usize> {
+    let digit = if base > 62 {
+        (c as u32).checked_sub(HIGH_ASCII_START)? as usize
+    } else {
+        let byte = u8::try_from(c).ok()?;
+        ALPHABET_62.iter().position(|&d| d == byte)?
+    };
+
+    (digit < base).then_some(digit)
+}
+
+fn is_token_char(c: char, base: usize) -> bool {
+    if base > 62 {
+        (HIGH_ASCII_START..=0xFF).contains(&(c as u32))
+    } else {
+        c.is_ascii_alphanumeric() || c == '_'
+    }
+}
+
+fn decode_token(token: &str, base: usize) -> Option<usize> {
+    token.chars().try_fold(0usize, |acc, c| {
+        acc.checked_mul(base)?.checked_add(digit_of(c, base)?)
+    })
+}
+
+/// 把範本中的每個 token 換成對應的關鍵字，空白關鍵字代表 token 即原字
+fn substitute(template: &str, base: usize, keywords: &[&str]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut token = String::new();
+
+    let flush = |token: &mut String, result: &mut String| {
+        if token.is_empty() {
+            return;
+        }
+
+        match decode_token(token, base).and_then(|idx| keywords.get(idx)) {
+            Some(keyword) if !keyword.is_empty() => result.push_str(keyword),
+            _ => result.push_str(token),
+        }
+
+        token.clear();
+    };
+
+    for c in template.chars() {
+        if is_token_char(c, base) {
+            token.push(c);
+        } else {
+            flush(&mut token, &mut result);
+            result.push(c);
+        }
+    }
+
+    flush(&mut token, &mut result);
+
+    result
+}
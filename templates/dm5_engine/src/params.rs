@@ -0,0 +1,126 @@
+use aidoku::{
+    Result, Viewer,
+    alloc::String,
+    imports::net::{HttpMethod, Request},
+    prelude::*,
+};
+
+use crate::helpers::{self, ChapterVars};
+
+/// 頁面選擇器，各站版型相同時直接沿用 [`Selectors::DM5`]
+pub struct Selectors {
+    pub list_item: &'static str,
+    pub list_cover: &'static str,
+    pub pagination: &'static str,
+    pub detail_cover: &'static str,
+    pub detail_title: &'static str,
+    pub detail_authors: &'static str,
+    pub detail_description: &'static str,
+    pub detail_tags: &'static str,
+    pub detail_status: &'static str,
+    /// 地區、題材連結，用來判斷閱讀方式
+    pub detail_labels: &'static str,
+    /// 章節分頁標籤（連載、單行本…）
+    pub chapter_tabs: &'static str,
+    /// 與分頁標籤依序對應的章節列表
+    pub chapter_lists: &'static str,
+    pub chapter_lock: &'static str,
+    pub chapter_title: &'static str,
+    /// 最後更新日期所在的元素
+    pub chapter_date: &'static str,
+    /// 詳情頁推薦區塊的標題，清單緊接在標題之後
+    pub section_title: &'static str,
+}
+
+impl Selectors {
+    pub const DM5: Self = Self {
+        list_item: "ul.mh-list > li",
+        list_cover: "p.mh-cover",
+        pagination: ".page-pagination a",
+        detail_cover: "div.banner_detail_form > div.cover > img",
+        detail_title: "div.banner_detail_form > div.info > p.title",
+        detail_authors: "div.banner_detail_form > div.info > p.subtitle > a",
+        detail_description: ".banner_detail_form>.info>.content",
+        detail_tags: ".banner_detail_form>.info>.tip>span.block:nth-child(2)>a",
+        detail_status: ".banner_detail_form>.info>.tip>span.block:nth-child(1)>span",
+        detail_labels: ".banner_detail_form>.info>.tip a",
+        chapter_tabs: ".detail-list-title > a.block",
+        chapter_lists: ".detail-list-select",
+        chapter_lock: ".info > .detail-lock",
+        chapter_title: ".info > .title",
+        chapter_date: ".detail-list-title",
+        section_title: ".index-title",
+    };
+}
+
+impl Default for Selectors {
+    fn default() -> Self {
+        Self::DM5
+    }
+}
+
+/// 引擎需要的站點資訊，由各來源依目前的設定建立
+pub struct Params {
+    /// 網站網址，不含結尾斜線，例如 `https://www.dm5.com`
+    pub base_url: String,
+    /// 深層連結接受的網域（含子網域），例如 `["dm5.com", "dm5.cn"]`
+    pub domains: &'static [&'static str],
+    /// 網站的語言參數，`1` 為簡體、`2` 為繁體
+    pub language: String,
+    pub selectors: &'static Selectors,
+    /// 列表沒有地區資訊時使用的閱讀方式
+    pub viewer: Viewer,
+    pub hide_locked_chapters: bool,
+    /// 逐頁解析圖片，交給 `get_image_request` 處理
+    pub lazy_pages: bool,
+    /// 以手機版閱讀章節
+    pub mobile: bool,
+    /// 從連結取出漫畫 key，不是漫畫連結時回傳 `None`
+    pub manga_key: fn(&str) -> Option<String>,
+    /// 建立帶有 User-Agent 與 Cookie 的 GET 請求
+    pub request: fn(String) -> Result<Request>,
+    /// 同上，使用手機版的 User-Agent
+    pub mobile_request: fn(String) -> Result<Request>,
+}
+
+impl Params {
+    /// 漫畫或章節的網址，例如 `manhua-xxx`、`m1217932`
+    pub fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+
+    /// 手機版的章節網址
+    pub fn mobile_url(&self, key: &str) -> String {
+        format!("{}/{}/", helpers::mobile_base_url(&self.base_url), key)
+    }
+
+    pub fn chapterfun_url(&self, vars: &ChapterVars, page: usize) -> String {
+        vars.chapterfun_url(&self.base_url, &self.language, page)
+    }
+
+    pub fn get(&self, url: String) -> Result<Request> {
+        (self.request)(url)
+    }
+
+    pub fn get_mobile(&self, url: String) -> Result<Request> {
+        (self.mobile_request)(url)
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            domains: &[],
+            language: String::from("1"),
+            selectors: &Selectors::DM5,
+            viewer: Viewer::Unknown,
+            hide_locked_chapters: false,
+            lazy_pages: true,
+            mobile: false,
+            manga_key: helpers::manga_key_of,
+            request: |url| Ok(Request::new(url, HttpMethod::Get)?),
+            mobile_request: |url| Ok(Request::new(url, HttpMethod::Get)?),
+        }
+    }
+}
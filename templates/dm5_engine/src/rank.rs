@@ -0,0 +1,83 @@
+use aidoku::{
+    Listing, ListingKind, Result,
+    alloc::{String, Vec},
+    prelude::*,
+};
+
+use crate::Params;
+
+/// 排行榜：(listing id, 網站的 `t`, 名稱)
+pub const BOARDS: &[(&str, &str, &str)] = &[
+    ("jpmanga", "2", "日漫排行"),
+    ("cnmanga", "1", "陸漫排行"),
+    ("allmanga", "3", "綜合排行"),
+    ("risemanga", "7", "上升最快"),
+    ("newmanga", "4", "新作排行"),
+    ("collectmanga", "5", "收藏排行"),
+    ("commentmanga", "6", "吐槽排行"),
+];
+
+/// 排行榜的時間範圍：(listing id 後綴, 網站的 `d`, 名稱)，沒有後綴時沿用網站預設
+pub const RANGES: &[(&str, &str, &str)] = &[
+    ("day", "1", "日榜"),
+    ("week", "2", "週榜"),
+    ("month", "3", "月榜"),
+    ("all", "4", "總榜"),
+];
+
+/// `dailymanga` 為新番更新，其餘為 `allmanga` 或帶時間後綴的 `allmanga-week`
+///
+/// 排行頁固定列出前幾名、沒有分頁，`page` 只用於新番更新
+pub fn url(params: &Params, listing_id: &str, page: i32) -> Result<String> {
+    if listing_id == "dailymanga" {
+        return Ok(if page > 1 {
+            format!("{}/manhua-new-p{}/", params.base_url, page)
+        } else {
+            format!("{}/manhua-new/", params.base_url)
+        });
+    }
+
+    let (board, range) = match listing_id.split_once('-') {
+        Some((board, range)) => {
+            let range = RANGES
+                .iter()
+                .find(|(name, _, _)| *name == range)
+                .map(|(_, id, _)| *id)
+                .ok_or_else(|| error!("Invalid listing"))?;
+
+            (board, Some(range))
+        }
+        None => (listing_id, None),
+    };
+
+    let id = BOARDS
+        .iter()
+        .find(|(name, _, _)| *name == board)
+        .map(|(_, id, _)| *id)
+        .ok_or_else(|| error!("Invalid listing"))?;
+
+    let mut url = format!("{}/manhua-rank/?t={}", params.base_url, id);
+
+    if let Some(range) = range {
+        url.push_str(&format!("&d={}", range));
+    }
+
+    Ok(url)
+}
+
+/// 各排行榜的日榜、週榜、月榜與總榜
+pub fn listings() -> Vec<Listing> {
+    let mut listings = Vec::new();
+
+    for (board, _, board_name) in BOARDS {
+        for (range, _, range_name) in RANGES {
+            listings.push(Listing {
+                id: format!("{}-{}", board, range),
+                name: format!("{}・{}", board_name, range_name),
+                kind: ListingKind::Default,
+            });
+        }
+    }
+
+    listings
+}
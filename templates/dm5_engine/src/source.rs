@@ -0,0 +1,209 @@
+use aidoku::{
+    DeepLinkResult, HashMap, Page, PageContext, Result, Viewer,
+    alloc::{String, Vec, string::ToString as _},
+    imports::{html::Document, net::Request},
+    prelude::*,
+};
+
+use core::cell::RefCell;
+
+use crate::{
+    Params,
+    helpers::{self, ChapterVars},
+    html::GenManga,
+    packer,
+};
+
+/// 各站來源共用的閱讀、深層連結與登入處理，來源的 trait 實作直接轉呼叫
+#[derive(Default)]
+pub struct Engine {
+    /// `{cid}-{page}` => 圖片網址，同一次 chapterfun 回傳的相鄰頁共用
+    page_cache: RefCell<HashMap<String, String>>,
+    /// 漫畫 key => 第一話閱讀模式對應的閱讀方式，同一部漫畫只讀取一次閱讀頁
+    read_modes: RefCell<HashMap<String, Option<Viewer>>>,
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 章節的頁面；選用手機版或電腦版解析失敗時改用手機版
+    pub fn page_list(&self, params: &Params, chapter_key: &str) -> Result<Vec<Page>> {
+        if params.mobile {
+            return Engine::mobile_page_list(params, chapter_key);
+        }
+
+        let url = params.url(chapter_key);
+
+        let response = params.get(url.clone())?.string()?;
+
+        if helpers::is_locked_chapter(&response) {
+            bail!("此章節需要付費解鎖");
+        }
+
+        // 簽章變數改版時電腦版會解析失敗，改用手機版
+        <Document as GenManga>::chapter(params, url, response)
+            .or_else(|error| Engine::mobile_page_list(params, chapter_key).map_err(|_| error))
+    }
+
+    /// 逐頁模式的圖片請求，`context` 帶有 chapterfun 需要的變數
+    pub fn image_request(
+        &self,
+        params: &Params,
+        url: String,
+        context: Option<PageContext>,
+    ) -> Result<Request> {
+        let url = match context.as_ref().and_then(ChapterVars::from_context) {
+            Some((vars, page)) => self.resolve_page(params, &vars, page)?,
+            None => url,
+        };
+
+        let cid = url
+            .split("cid=")
+            .nth(1)
+            .and_then(|s| s.split('&').next())
+            .unwrap_or("");
+
+        let referer = if cid.is_empty() {
+            url.clone()
+        } else {
+            params.url(&format!("m{}", cid))
+        };
+
+        Ok(params.get(url)?.header("Referer", &referer))
+    }
+
+    /// 地區與標籤無法判斷時，參考第一話閱讀頁的閱讀模式
+    pub fn read_mode_viewer(
+        &self,
+        params: &Params,
+        manga_key: &str,
+        document: &Document,
+    ) -> Option<Viewer> {
+        if let Some(viewer) = self.read_modes.borrow().get(manga_key) {
+            return *viewer;
+        }
+
+        let viewer = document
+            .select_first(".detail-list-select li a")
+            .and_then(|link| link.attr("href"))
+            .and_then(|href| params.get(params.url(href.trim_matches('/'))).ok())
+            .and_then(|request| request.string().ok())
+            .and_then(|body| helpers::viewer_from_read_mode(&body));
+
+        self.read_modes
+            .borrow_mut()
+            .insert(manga_key.to_string(), viewer);
+
+        viewer
+    }
+
+    /// 手機版閱讀頁，直接帶有整章的圖片陣列
+    fn mobile_chapter_body(params: &Params, key: &str) -> Result<String> {
+        let response = params.get_mobile(params.mobile_url(key))?.string()?;
+
+        if helpers::is_locked_chapter(&response) {
+            bail!("此章節需要付費解鎖");
+        }
+
+        Ok(response)
+    }
+
+    fn mobile_page_list(params: &Params, key: &str) -> Result<Vec<Page>> {
+        <Document as GenManga>::mobile_chapter(Engine::mobile_chapter_body(params, key)?)
+    }
+
+    /// 從 `page` 開始的幾頁圖片網址
+    fn chapterfun_images(params: &Params, vars: &ChapterVars, page: usize) -> Result<Vec<String>> {
+        let referer = params.url(&format!("m{}-p{}", vars.cid, page));
+
+        let packed = params
+            .get(params.chapterfun_url(vars, page))?
+            .header("Referer", &referer)
+            .string()?;
+
+        let decoded = packer::unpack(&packed).map_err(|err| error!("{}", err))?;
+
+        helpers::extract_image_urls(&decoded).ok_or_else(|| error!("Failed to extract image URLs"))
+    }
+
+    /// 解析單頁的圖片網址，一次 chapterfun 通常會帶回相鄰幾頁，一併快取
+    fn resolve_page(&self, params: &Params, vars: &ChapterVars, page: usize) -> Result<String> {
+        let cache_key = |page: usize| format!("{}-{}", vars.cid, page);
+
+        if let Some(url) = self.page_cache.borrow().get(&cache_key(page)) {
+            return Ok(url.clone());
+        }
+
+        // 簽章變數改版時 chapterfun 會失敗，改用手機版閱讀頁的整章圖片
+        let (first_page, urls) = match Engine::chapterfun_images(params, vars, page) {
+            Ok(urls) => (page, urls),
+            Err(error) => {
+                let body = Engine::mobile_chapter_body(params, &format!("m{}", vars.cid))
+                    .map_err(|_| error)?;
+
+                let urls = helpers::extract_mobile_images(&body)
+                    .ok_or_else(|| error!("No images found in mobile chapter page"))?;
+
+                (1, urls)
+            }
+        };
+
+        let mut page_cache = self.page_cache.borrow_mut();
+
+        // 只保留目前章節的快取
+        let prefix = format!("{}-", vars.cid);
+        page_cache.retain(|key, _| key.starts_with(&prefix));
+
+        for (offset, url) in urls.into_iter().enumerate() {
+            page_cache.insert(cache_key(first_page + offset), url);
+        }
+
+        page_cache
+            .get(&cache_key(page))
+            .cloned()
+            .ok_or_else(|| error!("No image for page {}", page))
+    }
+}
+
+/// 漫畫與章節網址的深層連結，不是 `params.domains` 的網址時回傳 `None`
+pub fn deep_link(params: &Params, url: &str) -> Result<Option<DeepLinkResult>> {
+    // https://www.dm5.com/manhua-xxx/ => manhua-xxx
+    // https://www.dm5.cn/m1217932-p2/ => m1217932-p2
+    let Some(segment) = helpers::link_segment(url, params.domains) else {
+        return Ok(None);
+    };
+
+    if let Some(key) = (params.manga_key)(segment) {
+        return Ok(Some(DeepLinkResult::Manga { key }));
+    }
+
+    let Some(key) = helpers::chapter_key_of(segment) else {
+        return Ok(None);
+    };
+
+    let body = params.get(params.url(&key))?.string()?;
+
+    let cid = helpers::extract_dm5_var(&body, "DM5_CID").ok_or_else(|| error!("No DM5_CID"))?;
+
+    // 漫畫以網址路徑為 key，DM5_MID 只是數字編號，無法直接對應
+    let manga_key = helpers::extract_dm5_var(&body, "DM5_COMIC_URL")
+        .and_then(params.manga_key)
+        .or_else(|| helpers::find_manga_link(&body))
+        .ok_or_else(|| error!("No manga found for DM5_CID={}", cid))?;
+
+    Ok(Some(DeepLinkResult::Chapter {
+        manga_key,
+        key: format!("m{}", cid),
+    }))
+}
+
+/// 以目前的 Cookie 讀取首頁，網站回報會員編號時才算登入成功
+///
+/// 登入頁在輸入帳號前就會發 Cookie，不能只看有沒有 Cookie
+pub fn verify_login(params: &Params) -> Result<bool> {
+    let body = params.get(format!("{}/", params.base_url))?.string()?;
+
+    Ok(helpers::user_id_of(&body).is_some())
+}
@@ -0,0 +1,416 @@
+#![expect(clippy::unwrap_used)]
+
+use super::*;
+use aidoku::{
+    Viewer,
+    alloc::{String, Vec, string::ToString as _, vec},
+};
+use aidoku_test::aidoku_test;
+
+use crate::filters::{FilterSelection, FilterTable, FilterTables};
+
+#[aidoku_test]
+fn test_chapter_key_of() {
+    assert_eq!(
+        helpers::chapter_key_of("m1217932"),
+        Some("m1217932".to_string())
+    );
+    assert_eq!(
+        helpers::chapter_key_of("/m1217932-p2/"),
+        Some("m1217932".to_string())
+    );
+    assert_eq!(helpers::chapter_key_of("manhua-yaoshenji"), None);
+    assert_eq!(helpers::chapter_key_of("m"), None);
+
+    let body =
+        r#"<div class="title"><a href="/">首頁</a><a href="/manhua-yaoshenji/">妖神記</a></div>"#;
+    assert_eq!(
+        helpers::find_manga_link(body),
+        Some("manhua-yaoshenji".to_string())
+    );
+}
+
+#[aidoku_test]
+fn test_parse_chapter_number() {
+    let cases = [
+        ("第1话", Some(1.0)),
+        ("第105話 決戰", Some(105.0)),
+        ("第12.5话", Some(12.5)),
+        ("第 36 话", Some(36.0)),
+        ("88话", Some(88.0)),
+        ("第十二回", Some(12.0)),
+        ("第一百零五话", Some(105.0)),
+        ("第两千话", Some(2000.0)),
+        ("第3卷 第20话", Some(20.0)),
+        ("番外1", Some(1.0)),
+        ("番外篇 第3话", Some(3.0)),
+        ("特别篇 2", Some(2.0)),
+        ("特别篇", None),
+        ("特別篇：夏日祭", None),
+        ("最后一回", None),
+        ("最終回", None),
+        ("公告", None),
+    ];
+
+    for (title, expected) in cases {
+        assert_eq!(helpers::parse_chapter_number(title), expected, "{}", title);
+    }
+}
+
+#[aidoku_test]
+fn test_parse_volume_number() {
+    let cases = [
+        ("第3卷", Some(3.0)),
+        ("第03卷", Some(3.0)),
+        ("第十卷", Some(10.0)),
+        ("第二冊", Some(2.0)),
+        ("卷05", Some(5.0)),
+        ("第3卷 第20话", Some(3.0)),
+        ("第20话", None),
+        ("番外卷", None),
+        ("上卷", None),
+    ];
+
+    for (title, expected) in cases {
+        assert_eq!(helpers::parse_volume_number(title), expected, "{}", title);
+    }
+}
+
+#[aidoku_test]
+fn test_parse_update_date() {
+    // 2024-03-15 12:00 (UTC+8)
+    let now = 1710475200;
+
+    let cases = [
+        ("最近于 今天 10:22 更新", Some(1710469320)),
+        ("昨天", Some(1710345600)),
+        ("03月01號", Some(1709222400)),
+        ("3月1号 更新至第20话", Some(1709222400)),
+        // 未來的月日視為去年
+        ("11月01號", Some(1698768000)),
+        ("2023-05-01", Some(1682870400)),
+        ("2024年2月29日 08:05", Some(1709165100)),
+        ("连载 单行本", None),
+        ("13月40號", None),
+    ];
+
+    for (text, expected) in cases {
+        assert_eq!(helpers::parse_update_date(text, now), expected, "{}", text);
+    }
+}
+
+#[aidoku_test]
+fn test_parse_page_count() {
+    assert_eq!(helpers::parse_page_count("第1话（20P）"), Some(20));
+    assert_eq!(helpers::parse_page_count("第1话 （18 P）"), Some(18));
+    assert_eq!(helpers::parse_page_count("第2话 (32p)"), Some(32));
+    assert_eq!(helpers::parse_page_count("第3P话"), None);
+    assert_eq!(helpers::parse_page_count("第4话"), None);
+}
+
+#[aidoku_test]
+fn test_extract_mobile_images() {
+    let packed = r#"<script>eval(function(p,a,c,k,e,d){e=function(c){return c};return p}('0 1=[\'2\',\'3\'];',10,4,'var|newImgs|https://a.cdnmanhua.net/1.jpg|https://a.cdnmanhua.net/2.jpg'.split('|'),0,{}))</script>"#;
+
+    assert_eq!(
+        helpers::extract_mobile_images(packed),
+        Some(vec![
+            "https://a.cdnmanhua.net/1.jpg".to_string(),
+            "https://a.cdnmanhua.net/2.jpg".to_string(),
+        ])
+    );
+
+    let plain = r#"<script>var newImgs=["https://a.cdnmanhua.net/3.jpg"];</script>"#;
+    assert_eq!(
+        helpers::extract_mobile_images(plain),
+        Some(vec!["https://a.cdnmanhua.net/3.jpg".to_string()])
+    );
+
+    assert_eq!(helpers::extract_mobile_images("<html></html>"), None);
+}
+
+#[aidoku_test]
+fn test_mobile_base_url() {
+    assert_eq!(
+        helpers::mobile_base_url("https://www.dm5.cn"),
+        "https://m.dm5.cn"
+    );
+    assert_eq!(
+        helpers::mobile_base_url("https://www.dm5.com/"),
+        "https://m.dm5.com"
+    );
+    assert_eq!(
+        helpers::mobile_base_url("https://dm5.com"),
+        "https://m.dm5.com"
+    );
+    assert_eq!(
+        helpers::mobile_base_url("https://m.dm5.com"),
+        "https://m.dm5.com"
+    );
+}
+
+#[aidoku_test]
+fn test_split_authors() {
+    let cases: [(&str, &[&str]); 8] = [
+        ("尾田荣一郎", &["尾田荣一郎"]),
+        ("原作:川口勇贵&漫画:大桥薰", &["川口勇贵", "大桥薰"]),
+        ("原作：稻垣理一郎 作畫：Boichi", &["稻垣理一郎", "Boichi"]),
+        ("ONE、村田雄介", &["ONE", "村田雄介"]),
+        ("藤本树 集英社", &["藤本树"]),
+        ("谏山创/讲谈社", &["谏山创"]),
+        ("Oda Eiichiro", &["Oda Eiichiro"]),
+        (" 堀越耕平 / ", &["堀越耕平"]),
+    ];
+
+    for (text, expected) in cases {
+        assert_eq!(helpers::split_authors(text), expected, "{}", text);
+    }
+}
+
+#[aidoku_test]
+fn test_detect_viewer() {
+    let labels = |labels: &[&str]| {
+        labels
+            .iter()
+            .map(|label| label.to_string())
+            .collect::<Vec<String>>()
+    };
+
+    let cases = [
+        (
+            labels(&["热血", "/manhua-jpkr/"]),
+            Some(Viewer::RightToLeft),
+        ),
+        (labels(&["日韓", "冒險"]), Some(Viewer::RightToLeft)),
+        (labels(&["大陆", "恋爱"]), Some(Viewer::Webtoon)),
+        (labels(&["日韩", "韩漫"]), Some(Viewer::Webtoon)),
+        (labels(&["港台", "武侠"]), Some(Viewer::LeftToRight)),
+        (labels(&["/manhua-euus/"]), Some(Viewer::LeftToRight)),
+        (labels(&["搞笑"]), None),
+    ];
+
+    for (labels, expected) in cases {
+        assert_eq!(helpers::detect_viewer(&labels), expected, "{:?}", labels);
+    }
+
+    assert_eq!(
+        helpers::viewer_from_read_mode("<script>var DM5_READMODE = 3;</script>"),
+        Some(Viewer::Webtoon)
+    );
+    assert_eq!(
+        helpers::viewer_from_read_mode("<script>var DM5_READMODE = 1;</script>"),
+        Some(Viewer::RightToLeft)
+    );
+    assert_eq!(
+        helpers::viewer_from_read_mode("<script>var DM5_READMODE = 2;</script>"),
+        None
+    );
+    assert_eq!(helpers::viewer_from_read_mode("<html></html>"), None);
+}
+
+#[aidoku_test]
+fn test_unpack_vectors() {
+    let cases = [
+        // dm5imagefun, base 36
+        (
+            r#"eval(function(p,a,c,k,e,d){e=function(c){return(c<a?'':e(parseInt(c/a)))+((c=c%a)>35?String.fromCharCode(c+29):c.toString(36))};if(!''.replace(/^/,String)){while(c--)d[e(c)]=k[c]||e(c);k=[function(e){return d[e]}];e=function(){return'\\w+'};c=1};while(c--)if(k[c])p=p.replace(new RegExp('\\b'+e(c)+'\\b','g'),k[c]);return p}('b 4(){0 5=3;0 6=\'7\';0 8="c://d-e-f-g-h.i.j/k/l/3";0 1=["/m.9","/n.9"];o(0 2=p;2<1.q;2++){1[2]=8+1[2]+\'?5=3&6=7&r=\'}s 1}0 a;a=4();',36,29,'var|pvalue|i|1217932|dm5imagefun|cid|key|8d2f1b|pix|jpg|d|function|https|manhua1034|104|250|150|12|cdndm5|com|34|33918|1_5831|2_4120|for|0|length|uk|return'.split('|'),0,{}))"#,
+            r#"function dm5imagefun(){var cid=1217932;var key='8d2f1b';var pix="https://manhua1034-104-250-150-12.cdndm5.com/34/33918/1217932";var pvalue=["/1_5831.jpg","/2_4120.jpg"];for(var i=0;i<pvalue.length;i++){pvalue[i]=pix+pvalue[i]+'?cid=1217932&key=8d2f1b&uk='}return pvalue}var d;d=dm5imagefun();"#,
+        ),
+        // base 62 uppercase
+        (
+            r#"eval(function(p,a,c,k,e,d){return p}('0 1=[\'2\',\'3\',\'4\',\'5\',\'6\',\'7\',\'8\',\'9\',\'a\',\'b\',\'c\',\'d\',\'e\',\'f\',\'g\',\'h\',\'i\',\'j\',\'k\',\'l\',\'m\',\'n\',\'o\',\'p\',\'q\',\'r\',\'s\',\'t\',\'u\',\'v\',\'w\',\'x\',\'y\',\'z\',\'A\',\'B\',\'C\',\'D\',\'E\',\'F\'];',62,42,'var|tags|t0|t1|t2|t3|t4|t5|t6|t7|t8|t9|t10|t11|t12|t13|t14|t15|t16|t17|t18|t19|t20|t21|t22|t23|t24|t25|t26|t27|t28|t29|t30|t31|t32|t33|t34|t35|t36|t37|t38|t39'.split('|'),0,{}))"#,
+            r#"var tags=['t0','t1','t2','t3','t4','t5','t6','t7','t8','t9','t10','t11','t12','t13','t14','t15','t16','t17','t18','t19','t20','t21','t22','t23','t24','t25','t26','t27','t28','t29','t30','t31','t32','t33','t34','t35','t36','t37','t38','t39'];"#,
+        ),
+        // [] base, (p,a,c,k,e,r)
+        (
+            r#"eval(function(p,a,c,k,e,r){e=String;if(!''.replace(/^/,String)){while(c--)r[c]=k[c]||c;k=[function(e){return r[e]}];e=function(){return'\\w+'};c=1};while(c--)if(k[c])p=p.replace(new RegExp('\\b'+e(c)+'\\b','g'),k[c]);return p}('0 6=[\'1://2.3.4/7.5\',\'1://2.3.4/8.5\'];0 9=[];',[],10,'var|https|a|cdnmanhua|net|jpg|newImgs|1|2|newImginfos'.split('|')))"#,
+            r#"var newImgs=['https://a.cdnmanhua.net/1.jpg','https://a.cdnmanhua.net/2.jpg'];var newImginfos=[];"#,
+        ),
+        // escapes
+        (
+            r#"eval(function(p,a,c,k,e,d){return p}('0 1=\'2\\\'3 "4"\';0 5=\'6:\\\\7\\\\\';0 8=\'9\n10\';',10,11,'var|msg|it|s|quoted|path|C|dm5|lines|a|b'.split('|'),0,{}))"#,
+            "var msg='it\\'s \"quoted\"';var path='C:\\\\dm5\\\\';var lines='a\nb';",
+        ),
+        // base 95 high ascii
+        (
+            "eval(function(p,a,c,k,e,d){return p}('\u{a1} \u{a4}=\u{a5};\u{a1} \u{a6}=\u{a2};\u{a1} \u{a7}=[\\'/\u{a8}.\u{a3}\\',\\'/\u{a2}.\u{a3}\\'];',95,8,'var|2|jpg|cid|1217932|page|images|1'.split('|'),0,{}))",
+            r#"var cid=1217932;var page=2;var images=['/1.jpg','/2.jpg'];"#,
+        ),
+    ];
+
+    for (packed, expected) in cases {
+        assert_eq!(packer::unpack(packed).as_deref(), Ok(expected));
+    }
+
+    // 前後夾雜其他程式碼
+    let html = format!("<script>var a=1;</script><script>{}</script>", cases[2].0);
+    assert_eq!(packer::unpack(&html).as_deref(), Ok(cases[2].1));
+}
+
+#[aidoku_test]
+fn test_unpack_chapterfun() {
+    // chapterfun.ashx 回應的完整格式：雙引號的 e(c)、`c=1;}`、`return p;}`，
+    // 以及編碼後與自身相同的關鍵字留空（0、d、i）
+    //
+    // TODO: 這是用 Dean Edwards 演算法自行打包的範例，不是網站的實際回應；
+    // 存下一份 chapterfun.ashx 的回應後改用實際內容
+    let packed = r#"eval(function(p,a,c,k,e,d){e=function(c){return(c<a?"":e(parseInt(c/a)))+((c=c%a)>35?String.fromCharCode(c+29):c.toString(36))};if(!''.replace(/^/,String)){while(c--)d[e(c)]=k[c]||e(c);k=[function(e){return d[e]}];e=function(){return'\\w+'};c=1;};while(c--)if(k[c])p=p.replace(new RegExp('\\b'+e(c)+'\\b','g'),k[c]);return p;}('a 4(){1 5=3;1 6=\'7\';1 8="b://c-e-f-g-h.j.k/l/m/3";1 2=["/n.9","/o.9"];p(1 i=0;i<2.q;i++){2[i]=8+2[i]+\'?5=3&6=7&r=\'}s 2}1 d;d=4();',62,29,'|var|pvalue|1217932|dm5imagefun|cid|key|3f5c0a9e2b7d41e6a8c9d0b1e2f3a4b5|pix|jpg|function|https|manhua1034||104|250|150|12||cdndm5|com|34|33918|1_5831|2_4120|for|length|uk|return'.split('|'),0,{}))"#;
+
+    let decoded = packer::unpack(packed).unwrap();
+
+    assert_eq!(
+        helpers::extract_image_urls(&decoded),
+        Some(vec![
+            "https://manhua1034-104-250-150-12.cdndm5.com/34/33918/1217932/1_5831.jpg?cid=1217932&key=3f5c0a9e2b7d41e6a8c9d0b1e2f3a4b5".to_string(),
+            "https://manhua1034-104-250-150-12.cdndm5.com/34/33918/1217932/2_4120.jpg?cid=1217932&key=3f5c0a9e2b7d41e6a8c9d0b1e2f3a4b5".to_string(),
+        ])
+    );
+}
+
+#[aidoku_test]
+fn test_unpack_errors() {
+    use packer::UnpackError;
+
+    let cases = [
+        ("var a=1;", UnpackError::NotPacked),
+        (
+            "eval(function(p,a,c,k,e,d){return p}('0 1',10,3,'a|b'.split('|'),0,{}))",
+            UnpackError::SymtabMismatch {
+                count: 3,
+                keywords: 2,
+            },
+        ),
+        (
+            "eval(function(p,a,c,k,e,d){return p}('0',96,1,'a'.split('|'),0,{}))",
+            UnpackError::UnsupportedBase(96),
+        ),
+        (
+            "eval(function(p,a,c,k,e,d){return p}('0 1",
+            UnpackError::UnterminatedString,
+        ),
+        (
+            "eval(function(p,a,c,k,e,d){return p}('0',x,1,'a'.split('|'),0,{}))",
+            UnpackError::MalformedArguments("base"),
+        ),
+        (
+            "eval(function(p,a,c,k,e,d){return p}('0',10,1,'a',0,{}))",
+            UnpackError::MalformedArguments("keywords"),
+        ),
+    ];
+
+    for (packed, expected) in cases {
+        assert_eq!(packer::unpack(packed), Err(expected), "{}", packed);
+    }
+}
+
+#[aidoku_test]
+fn test_filter_tables_path() {
+    const fn table(
+        ids: &'static [&'static str],
+        slugs: &'static [&'static str],
+        names: &'static [&'static str],
+    ) -> FilterTable {
+        FilterTable {
+            ids,
+            slugs,
+            hans: names,
+            hant: names,
+        }
+    }
+
+    // 只有地區與題材有單一篩選網址的小站
+    let tables = FilterTables {
+        areas: table(&["", "area1"], &["", "jp"], &["全部", "日本"]),
+        words: table(&[], &[], &[]),
+        tags: table(&["", "tag1"], &["", "rexue"], &["全部", "热血"]),
+        audiences: table(&[], &[], &[]),
+        pays: table(&[], &[], &[]),
+        statuses: table(&["", "st1"], &[], &["全部", "连载中"]),
+    };
+
+    let path = |area: &str, tag: &str, status: &str, sort: &str| {
+        let selection = FilterSelection {
+            area: area.to_string(),
+            tag: tag.to_string(),
+            status: status.to_string(),
+            sort: sort.to_string(),
+            ..Default::default()
+        };
+
+        tables.path(&selection, false, 2)
+    };
+
+    assert_eq!(path("", "", "", ""), "-list-p2");
+    assert_eq!(path("全部", "", "", "s2"), "-list-s2-p2");
+    assert_eq!(path("日本", "", "", ""), "-jp-p2");
+    assert_eq!(path("", "热血", "", "s2"), "-rexue-s2-p2");
+    assert_eq!(path("日本", "热血", "", ""), "-list-area1-tag1-p2");
+    assert_eq!(path("", "", "连载中", ""), "-list-st1-p2");
+    // 不認得的名稱直接略過
+    assert_eq!(path("", "未知", "连载中", ""), "-list-st1-p2");
+}
+
+#[aidoku_test]
+fn test_user_id_of() {
+    assert_eq!(
+        helpers::user_id_of("var DM5_USERID=123456;\nvar DM5_USERNAME=\"abc\";"),
+        Some(123456)
+    );
+    assert_eq!(helpers::user_id_of("var DM5_USERID = 0;"), None);
+    assert_eq!(helpers::user_id_of("var DM5_CID=1217932;"), None);
+}
+
+#[aidoku_test]
+fn test_rank_paths() {
+    let params = Params {
+        base_url: "https://www.dm5.com".to_string(),
+        ..Default::default()
+    };
+
+    let cases = [
+        ("dailymanga", 1, "/manhua-new/"),
+        ("dailymanga", 2, "/manhua-new-p2/"),
+        ("jpmanga", 1, "/manhua-rank/?t=2"),
+        // 排行頁沒有分頁
+        ("allmanga", 3, "/manhua-rank/?t=3"),
+        ("allmanga-week", 1, "/manhua-rank/?t=3&d=2"),
+        ("collectmanga-all", 1, "/manhua-rank/?t=5&d=4"),
+        ("commentmanga-day", 1, "/manhua-rank/?t=6&d=1"),
+    ];
+
+    for (listing_id, page, expected) in cases {
+        let url = rank::url(&params, listing_id, page).unwrap();
+
+        assert_eq!(url.strip_prefix("https://www.dm5.com"), Some(expected));
+    }
+
+    assert!(rank::url(&params, "allmanga-year", 1).is_err());
+    assert!(rank::url(&params, "unknown", 1).is_err());
+
+    assert_eq!(
+        rank::listings().len(),
+        rank::BOARDS.len() * rank::RANGES.len()
+    );
+}
+
+#[aidoku_test]
+fn test_deep_link_without_network() {
+    let params = Params {
+        base_url: "https://www.dm5.com".to_string(),
+        domains: &["dm5.com", "dm5.cn"],
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        deep_link(&params, "https://www.dm5.cn/manhua-yaoshenji/?from=share"),
+        Ok(Some(aidoku::DeepLinkResult::Manga { ref key })) if key == "manhua-yaoshenji"
+    ));
+    assert!(matches!(
+        deep_link(&params, "https://notdm5.com/manhua-yaoshenji/"),
+        Ok(None)
+    ));
+
+    assert_eq!(params.url("m1217932"), "https://www.dm5.com/m1217932");
+    assert_eq!(params.mobile_url("m1217932"), "https://m.dm5.com/m1217932/");
+    assert!(params.lazy_pages);
+}